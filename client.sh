@@ -34,9 +34,9 @@ case $NETWORK in
 esac
 
 
-transfer-ownership() {
+execute() {
   sender=$1
-  msg='{"transfer_ownership":{}}'
+  msg=$2
   flags="\
   --node $NODE \
   --gas-prices 0.025$DENOM \
@@ -54,6 +54,43 @@ transfer-ownership() {
 }
 
 
+propose-manager() {
+  sender=$1
+  address=$2
+  execute $sender '{"propose_manager":{"address":"'$address'"}}'
+}
+
+
+accept-manager() {
+  sender=$1
+  execute $sender '{"accept_manager":{}}'
+}
+
+
+query-pending-manager() {
+  query='{"pending_manager":{}}'
+  flags="--chain-id $CHAIN_ID --output json --node $NODE"
+  echo junod query wasm contract-state smart $CONTRACT_ADDR "$query" $flags
+  response=$(junod query wasm contract-state smart $CONTRACT_ADDR "$query" $flags)
+  echo $response | ./bin/utils/base64-decode-attributes | jq
+}
+
+
+crank() {
+  sender=$1
+  execute $sender '{"crank":{"limit":null}}'
+}
+
+
+query-crank() {
+  query='{"crank":{}}'
+  flags="--chain-id $CHAIN_ID --output json --node $NODE"
+  echo junod query wasm contract-state smart $CONTRACT_ADDR "$query" $flags
+  response=$(junod query wasm contract-state smart $CONTRACT_ADDR "$query" $flags)
+  echo $response | ./bin/utils/base64-decode-attributes | jq
+}
+
+
 query-select() {
   query='{"select":{"fields":null}}'
   flags="--chain-id $CHAIN_ID --output json --node $NODE"
@@ -66,8 +103,20 @@ set -e
 echo "executing $CMD for $CONTRACT_ADDR"
 
 case $CMD in
-  transfer-ownership)
-    transfer-ownership $1
+  propose-manager)
+    propose-manager $1 $2
+    ;;
+  accept-manager)
+    accept-manager $1
+    ;;
+  query-pending-manager)
+    query-pending-manager
+    ;;
+  crank)
+    crank $1
+    ;;
+  query-crank)
+    query-crank
     ;;
   query-select) 
     query-select
@@ -1,16 +1,32 @@
 use crate::error::ContractError;
+use crate::execute::admin::exec_admin;
+use crate::execute::cancel_unbonding::exec_cancel_unbonding;
 use crate::execute::claim::exec_claim;
+use crate::execute::compound::{exec_compound, exec_set_auto_compound};
+use crate::execute::crank::exec_crank;
 use crate::execute::deposit::exec_deposit;
+use crate::execute::manager::{
+    exec_accept_manager, exec_cancel_manager_proposal, exec_propose_manager,
+};
+use crate::execute::payout::exec_request_payout;
+use crate::execute::receive::exec_receive;
 use crate::execute::stake::exec_stake;
+use crate::execute::sync_account::exec_sync_account;
+use crate::execute::taxes::{exec_claim_taxes, exec_update_tax_recipient_info};
 use crate::execute::unstake::exec_unstake;
 use crate::execute::Context;
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, SudoMsg};
 use crate::query::account::query_account;
-use crate::query::deposits::query_deposits;
+use crate::query::accounts::{query_accounts, query_top_stakers};
+use crate::query::config::query_config;
+use crate::query::crank::query_crank;
+use crate::query::deposits::{query_depositor, query_deposits};
 use crate::query::house::query_house;
+use crate::query::manager::query_pending_manager;
 use crate::query::taxes::query_taxes;
 use crate::query::ReadonlyContext;
 use crate::state;
+use crate::sudo::sudo_clock_end_block;
 use cosmwasm_std::{entry_point, to_json_binary};
 use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response};
 use cw2::set_contract_version;
@@ -26,7 +42,7 @@ pub fn instantiate(
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    Ok(state::init(Context { deps, env, info }, msg)?)
+    state::init(Context { deps, env, info }, msg)
 }
 
 #[entry_point]
@@ -39,10 +55,35 @@ pub fn execute(
     let ctx = Context { deps, env, info };
 
     match msg {
+        ExecuteMsg::Admin(msg) => exec_admin(ctx, msg),
+        ExecuteMsg::Receive(msg) => exec_receive(ctx, msg),
         ExecuteMsg::Deposit(msg) => exec_deposit(ctx, msg),
         ExecuteMsg::Stake(msg) => exec_stake(ctx, msg),
         ExecuteMsg::Unstake(msg) => exec_unstake(ctx, msg),
+        ExecuteMsg::CancelUnbonding { amount } => exec_cancel_unbonding(ctx, amount),
         ExecuteMsg::Claim(msg) => exec_claim(ctx, msg),
+        ExecuteMsg::Compound {} => exec_compound(ctx),
+        ExecuteMsg::SetAutoCompound { enabled } => exec_set_auto_compound(ctx, enabled),
+        ExecuteMsg::ClaimTaxes { tokens } => exec_claim_taxes(ctx, tokens),
+        ExecuteMsg::UpdateTaxRecipientInfo { name, logo } => {
+            exec_update_tax_recipient_info(ctx, name, logo)
+        },
+        ExecuteMsg::ProposeManager {
+            address,
+            expires_at,
+        } => exec_propose_manager(ctx, address, expires_at),
+        ExecuteMsg::AcceptManager {} => exec_accept_manager(ctx),
+        ExecuteMsg::CancelManagerProposal {} => exec_cancel_manager_proposal(ctx),
+        ExecuteMsg::SyncAccount {
+            address,
+            max_events,
+        } => exec_sync_account(ctx, address, max_events),
+        ExecuteMsg::Crank { limit } => exec_crank(ctx, limit),
+        ExecuteMsg::RequestPayout {
+            recipient,
+            token,
+            amount,
+        } => exec_request_payout(ctx, recipient, token, amount),
     }
 }
 
@@ -55,13 +96,36 @@ pub fn query(
     let ctx = ReadonlyContext { deps, env };
     let result = match msg {
         QueryMsg::Account { address } => to_json_binary(&query_account(ctx, address)?),
+        QueryMsg::Accounts { start_after, limit } => {
+            to_json_binary(&query_accounts(ctx, start_after, limit)?)
+        },
+        QueryMsg::TopStakers { limit } => to_json_binary(&query_top_stakers(ctx, limit)?),
         QueryMsg::House {} => to_json_binary(&query_house(ctx)?),
+        QueryMsg::Config {} => to_json_binary(&query_config(ctx)?),
         QueryMsg::Taxes {} => to_json_binary(&query_taxes(ctx)?),
-        QueryMsg::Deposits {} => to_json_binary(&query_deposits(ctx)?),
+        QueryMsg::Deposits {
+            token,
+            start_after,
+            limit,
+        } => to_json_binary(&query_deposits(ctx, token, start_after, limit)?),
+        QueryMsg::Depositor { address } => to_json_binary(&query_depositor(ctx, address)?),
+        QueryMsg::PendingManager {} => to_json_binary(&query_pending_manager(ctx)?),
+        QueryMsg::Crank {} => to_json_binary(&query_crank(ctx)?),
     }?;
     Ok(result)
 }
 
+#[entry_point]
+pub fn sudo(
+    deps: DepsMut,
+    env: Env,
+    msg: SudoMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        SudoMsg::ClockEndBlock {} => sudo_clock_end_block(deps, env),
+    }
+}
+
 #[entry_point]
 pub fn migrate(
     deps: DepsMut,
@@ -69,5 +133,6 @@ pub fn migrate(
     _msg: MigrateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    state::migrate(deps.storage)?;
     Ok(Response::default())
 }
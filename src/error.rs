@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -11,6 +11,15 @@ pub enum ContractError {
 
   #[error("ValidationError: {reason:?}")]
   ValidationError { reason: String },
+
+  #[error("BelowMinStakeIncrement: {amount} is less than the minimum increment {min_increment}")]
+  BelowMinStakeIncrement {
+    amount: Uint128,
+    min_increment: Uint128,
+  },
+
+  #[error("SyncRequired: account has more than {max_events} pending events, execute SyncAccount first")]
+  SyncRequired { max_events: u32 },
 }
 
 impl From<ContractError> for StdError {
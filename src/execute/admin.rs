@@ -0,0 +1,397 @@
+use crate::{
+    error::ContractError,
+    msg::{
+        AdminMsg, AmortizationConfig, DepositorInfo, HouseMarketingInfo, InstantUnstakeConfig,
+        PayoutConfig,
+    },
+    state::storage::{
+        AMORTIZATION_CONFIG, BURN_PCT, DEPOSITORS, DEPOSITOR_ALLOWLIST_ENABLED, GAMES,
+        INSTANT_UNSTAKE_CONFIG, MARKETING_INFO, MIN_STAKE_INCREMENT, PAYOUT_CONFIG,
+        REVENUE_TOKEN_KEYS, UNBONDING_SECONDS,
+    },
+    sync::MAX_CRANK_LIMIT,
+    token::Token,
+};
+use cosmwasm_std::{attr, Addr, Api, Response, Uint128, Uint64};
+
+use super::{
+    ensure_burnable, ensure_manager,
+    slash::{exec_set_slash_recipient, exec_slash, exec_slash_account},
+    taxes::{exec_add_tax_recipient, exec_remove_tax_recipient, exec_update_tax_recipient},
+    validate_text, Context,
+};
+
+/// Max length of marketing name
+const MAX_NAME_LEN: usize = 64;
+
+/// Max length of marketing description
+const MAX_DESCRIPTION_LEN: usize = 1_000;
+
+/// Max length of marketing logo URL
+const MAX_LOGO_LEN: usize = 500;
+
+/// Max length of depositor label
+const MAX_LABEL_LEN: usize = 64;
+
+/// Max unbonding period (365 days)
+const MAX_UNBONDING_SECONDS: u64 = 60 * 60 * 24 * 365;
+
+pub fn exec_admin(
+    ctx: Context,
+    msg: AdminMsg,
+) -> Result<Response, ContractError> {
+    ensure_manager(ctx.deps.storage, &ctx.info.sender)?;
+
+    match msg {
+        AdminMsg::UpdateMarketingInfo(marketing) => exec_update_marketing_info(ctx, marketing),
+        AdminMsg::UpdateUnbondingSeconds { seconds } => exec_update_unbonding_seconds(ctx, seconds),
+        AdminMsg::SetMinIncrement { amount } => exec_set_min_increment(ctx, amount),
+        AdminMsg::AddRevenueToken { token } => exec_add_revenue_token(ctx, token),
+        AdminMsg::RemoveRevenueToken { token } => exec_remove_revenue_token(ctx, token),
+        AdminMsg::AddTaxRecipient(args) => exec_add_tax_recipient(ctx, args),
+        AdminMsg::UpdateTaxRecipient {
+            address,
+            pct,
+            autosend,
+            immutable,
+        } => exec_update_tax_recipient(ctx, address, pct, autosend, immutable),
+        AdminMsg::RemoveTaxRecipient { address } => exec_remove_tax_recipient(ctx, address),
+        AdminMsg::SetInstantUnstakeConfig { config } => {
+            exec_set_instant_unstake_config(ctx, config)
+        },
+        AdminMsg::SetAmortizationConfig { config } => exec_set_amortization_config(ctx, config),
+        AdminMsg::AddGame { address } => exec_add_game(ctx, address),
+        AdminMsg::RemoveGame { address } => exec_remove_game(ctx, address),
+        AdminMsg::SetPayoutConfig { config } => exec_set_payout_config(ctx, config),
+        AdminMsg::SetDepositor { address, info } => exec_set_depositor(ctx, address, info),
+        AdminMsg::RemoveDepositor { address } => exec_remove_depositor(ctx, address),
+        AdminMsg::SetDepositorAllowlist { enabled } => exec_set_depositor_allowlist(ctx, enabled),
+        AdminMsg::SetSlashRecipient { address } => exec_set_slash_recipient(ctx, address),
+        AdminMsg::Slash { ratio } => exec_slash(ctx, ratio),
+        AdminMsg::SlashAccount { address, amount } => exec_slash_account(ctx, address, amount),
+    }
+}
+
+fn exec_update_marketing_info(
+    ctx: Context,
+    marketing: HouseMarketingInfo,
+) -> Result<Response, ContractError> {
+    let Context { deps, .. } = ctx;
+
+    validate_text("name", &marketing.name, MAX_NAME_LEN)?;
+    validate_text("description", &marketing.description, MAX_DESCRIPTION_LEN)?;
+    validate_text("logo", &marketing.logo, MAX_LOGO_LEN)?;
+
+    MARKETING_INFO.save(deps.storage, &marketing)?;
+
+    // Omit unset fields, since attribute values can't be empty
+    let mut attrs = vec![attr("action", "update_marketing_info")];
+    for (key, value) in [
+        ("name", marketing.name),
+        ("description", marketing.description),
+        ("logo", marketing.logo),
+    ] {
+        if let Some(value) = value {
+            attrs.push(attr(key, value));
+        }
+    }
+
+    Ok(Response::new().add_attributes(attrs))
+}
+
+fn exec_update_unbonding_seconds(
+    ctx: Context,
+    seconds: Uint64,
+) -> Result<Response, ContractError> {
+    let Context { deps, .. } = ctx;
+
+    if seconds.u64() > MAX_UNBONDING_SECONDS {
+        return Err(ContractError::ValidationError {
+            reason: format!("unbonding seconds cannot exceed {}", MAX_UNBONDING_SECONDS),
+        });
+    }
+
+    let prev_seconds = UNBONDING_SECONDS.load(deps.storage)?;
+
+    UNBONDING_SECONDS.save(deps.storage, &seconds)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_unbonding_seconds"),
+        attr("prev_seconds", prev_seconds.to_string()),
+        attr("seconds", seconds.to_string()),
+    ]))
+}
+
+fn exec_set_min_increment(
+    ctx: Context,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let Context { deps, .. } = ctx;
+
+    let prev_amount = MIN_STAKE_INCREMENT
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+
+    MIN_STAKE_INCREMENT.save(deps.storage, &amount)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "set_min_increment"),
+        attr("prev_amount", prev_amount.to_string()),
+        attr("amount", amount.to_string()),
+    ]))
+}
+
+fn exec_add_revenue_token(
+    ctx: Context,
+    token: Token,
+) -> Result<Response, ContractError> {
+    let Context { deps, env, .. } = ctx;
+
+    validate_token(deps.api, &token)?;
+
+    // Deposits of every revenue token are partially burned if BURN_PCT is set
+    if !BURN_PCT
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .is_zero()
+    {
+        ensure_burnable(&token, &env.contract.address)?;
+    }
+
+    let token_key = token.to_key();
+
+    if REVENUE_TOKEN_KEYS.has(deps.storage, &token_key) {
+        return Err(ContractError::ValidationError {
+            reason: "revenue token already exists".to_owned(),
+        });
+    }
+
+    REVENUE_TOKEN_KEYS.save(deps.storage, &token_key, &0)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "add_revenue_token"),
+        attr("token", token_key),
+    ]))
+}
+
+fn exec_remove_revenue_token(
+    ctx: Context,
+    token: Token,
+) -> Result<Response, ContractError> {
+    let Context { deps, .. } = ctx;
+    let token_key = token.to_key();
+
+    // NOTE: Balances already synced to accounts remain claimable. Removal
+    // only prevents future deposits of this token.
+    if !REVENUE_TOKEN_KEYS.has(deps.storage, &token_key) {
+        return Err(ContractError::ValidationError {
+            reason: "revenue token not found".to_owned(),
+        });
+    }
+
+    REVENUE_TOKEN_KEYS.remove(deps.storage, &token_key);
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "remove_revenue_token"),
+        attr("token", token_key),
+    ]))
+}
+
+fn exec_set_instant_unstake_config(
+    ctx: Context,
+    config: Option<InstantUnstakeConfig>,
+) -> Result<Response, ContractError> {
+    let Context { deps, .. } = ctx;
+
+    let mut attrs = vec![attr("action", "set_instant_unstake_config")];
+
+    if let Some(config) = config {
+        if config.penalty_pct > Uint128::from(1_000_000u128) {
+            return Err(ContractError::ValidationError {
+                reason: "penalty rate cannot exceed 1000000 or 100%".to_owned(),
+            });
+        }
+        INSTANT_UNSTAKE_CONFIG.save(deps.storage, &config)?;
+        attrs.push(attr("penalty_pct", config.penalty_pct.to_string()));
+        attrs.push(attr("taxed", config.taxed.to_string()));
+    } else {
+        INSTANT_UNSTAKE_CONFIG.remove(deps.storage);
+        attrs.push(attr("enabled", "false"));
+    }
+
+    Ok(Response::new().add_attributes(attrs))
+}
+
+fn exec_set_amortization_config(
+    ctx: Context,
+    config: AmortizationConfig,
+) -> Result<Response, ContractError> {
+    let Context { deps, .. } = ctx;
+
+    if config.batch_size > MAX_CRANK_LIMIT {
+        return Err(ContractError::ValidationError {
+            reason: format!("batch size cannot exceed {}", MAX_CRANK_LIMIT),
+        });
+    }
+    if config.account_pct > Uint128::from(1_000_000u128) {
+        return Err(ContractError::ValidationError {
+            reason: "account share cannot exceed 1000000 or 100%".to_owned(),
+        });
+    }
+
+    AMORTIZATION_CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "set_amortization_config"),
+        attr("batch_size", config.batch_size.to_string()),
+        attr("account_pct", config.account_pct.to_string()),
+    ]))
+}
+
+fn exec_add_game(
+    ctx: Context,
+    address: Addr,
+) -> Result<Response, ContractError> {
+    let Context { deps, .. } = ctx;
+
+    deps.api.addr_validate(address.as_str())?;
+
+    if GAMES.has(deps.storage, &address) {
+        return Err(ContractError::ValidationError {
+            reason: "game already exists".to_owned(),
+        });
+    }
+
+    GAMES.save(deps.storage, &address, &0)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "add_game"),
+        attr("address", address.to_string()),
+    ]))
+}
+
+fn exec_remove_game(
+    ctx: Context,
+    address: Addr,
+) -> Result<Response, ContractError> {
+    let Context { deps, .. } = ctx;
+
+    if !GAMES.has(deps.storage, &address) {
+        return Err(ContractError::ValidationError {
+            reason: "game not found".to_owned(),
+        });
+    }
+
+    GAMES.remove(deps.storage, &address);
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "remove_game"),
+        attr("address", address.to_string()),
+    ]))
+}
+
+fn exec_set_payout_config(
+    ctx: Context,
+    config: Option<PayoutConfig>,
+) -> Result<Response, ContractError> {
+    let Context { deps, .. } = ctx;
+
+    let mut attrs = vec![attr("action", "set_payout_config")];
+
+    if let Some(config) = config {
+        if config.max_payout_pct >= Uint128::from(1_000_000u128)
+            || config.max_window_pct >= Uint128::from(1_000_000u128)
+        {
+            return Err(ContractError::ValidationError {
+                reason: "payout rates must be less than 1000000 or 100%".to_owned(),
+            });
+        }
+        if config.window_seconds.is_zero() {
+            return Err(ContractError::ValidationError {
+                reason: "payout window cannot be empty".to_owned(),
+            });
+        }
+        PAYOUT_CONFIG.save(deps.storage, &config)?;
+        attrs.push(attr("max_payout_pct", config.max_payout_pct.to_string()));
+        attrs.push(attr("window_seconds", config.window_seconds.to_string()));
+        attrs.push(attr("max_window_pct", config.max_window_pct.to_string()));
+    } else {
+        PAYOUT_CONFIG.remove(deps.storage);
+        attrs.push(attr("enabled", "false"));
+    }
+
+    Ok(Response::new().add_attributes(attrs))
+}
+
+fn exec_set_depositor(
+    ctx: Context,
+    address: Addr,
+    info: DepositorInfo,
+) -> Result<Response, ContractError> {
+    let Context { deps, .. } = ctx;
+
+    deps.api.addr_validate(address.as_str())?;
+    validate_text("label", &Some(info.label.to_owned()), MAX_LABEL_LEN)?;
+
+    DEPOSITORS.save(deps.storage, &address, &info)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "set_depositor"),
+        attr("address", address.to_string()),
+        attr("label", info.label),
+        attr("category", info.category.to_key()),
+    ]))
+}
+
+fn exec_remove_depositor(
+    ctx: Context,
+    address: Addr,
+) -> Result<Response, ContractError> {
+    let Context { deps, .. } = ctx;
+
+    if !DEPOSITORS.has(deps.storage, &address) {
+        return Err(ContractError::ValidationError {
+            reason: "depositor not found".to_owned(),
+        });
+    }
+
+    DEPOSITORS.remove(deps.storage, &address);
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "remove_depositor"),
+        attr("address", address.to_string()),
+    ]))
+}
+
+fn exec_set_depositor_allowlist(
+    ctx: Context,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    let Context { deps, .. } = ctx;
+
+    DEPOSITOR_ALLOWLIST_ENABLED.save(deps.storage, &enabled)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "set_depositor_allowlist"),
+        attr("enabled", enabled.to_string()),
+    ]))
+}
+
+fn validate_token(
+    api: &dyn Api,
+    token: &Token,
+) -> Result<(), ContractError> {
+    match token {
+        Token::Address(addr) => {
+            api.addr_validate(addr.as_str())?;
+        },
+        Token::Denom(denom) => {
+            if denom.trim().is_empty() {
+                return Err(ContractError::ValidationError {
+                    reason: "denom cannot be empty".to_owned(),
+                });
+            }
+        },
+    }
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use crate::{
+    error::ContractError,
+    math::{add_u128, sub_u128, sum_u128},
+    state::storage::{ACCOUNTS, SEQ_NO, TOTAL_DELEGATION, TOTAL_UNBONDING},
+    sync::{
+        amortize, load_delegation_scale, load_unbondings, persist_sync_results, pin_legacy_weight,
+        save_unbondings, sync_account, update_account_weight, MAX_IMPLICIT_SYNC_EVENTS,
+    },
+};
+use cosmwasm_std::{attr, Response, Uint128};
+
+use super::Context;
+
+/// Move unbonding funds back into the sender's delegation, taking from the
+/// most recently created tranches first.
+pub fn exec_cancel_unbonding(
+    ctx: Context,
+    maybe_amount: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let Context { deps, env, info } = ctx;
+    let seq_no = SEQ_NO.load(deps.storage)?;
+
+    let mut account = ACCOUNTS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or_else(|| ContractError::NotAuthorized {
+            reason: "Account not found".to_owned(),
+        })?;
+
+    let mut tranches = load_unbondings(deps.storage, &info.sender)?;
+
+    let total_unbonding = sum_u128(tranches.iter().map(|t| t.amount).collect())?;
+    let amount = maybe_amount.unwrap_or(total_unbonding);
+
+    if amount.is_zero() || amount > total_unbonding {
+        return Err(ContractError::ValidationError {
+            reason: format!(
+                "amount must be greater than zero and at most {}",
+                total_unbonding
+            ),
+        });
+    }
+
+    // Eagerly sync account before adding new delegation
+    let results = sync_account(
+        deps.storage,
+        &info.sender,
+        &account,
+        seq_no,
+        None,
+        Some(MAX_IMPLICIT_SYNC_EVENTS),
+    )?;
+    pin_legacy_weight(deps.storage, &info.sender, &account, &results, seq_no)?;
+
+    for (result, state) in results.iter() {
+        persist_sync_results(deps.storage, &info.sender, result, state)?;
+    }
+
+    // Remove the amount from the newest tranches first
+    let mut remaining = amount;
+    while !remaining.is_zero() {
+        let tranche = tranches.last_mut().unwrap();
+        if tranche.amount <= remaining {
+            remaining = sub_u128(remaining, tranche.amount)?;
+            tranches.pop();
+        } else {
+            tranche.amount = sub_u128(tranche.amount, remaining)?;
+            remaining = Uint128::zero();
+        }
+    }
+
+    save_unbondings(deps.storage, &info.sender, &tranches)?;
+
+    // Apply any reduction of delegation by payouts since the last rescale
+    account.rescale(load_delegation_scale(deps.storage)?)?;
+
+    let prev_weight = account.weight()?;
+
+    account.release_expired_locks(env.block.time);
+    account.add_delegation(amount)?;
+
+    ACCOUNTS.save(deps.storage, &info.sender, &account)?;
+
+    // Move the amount from total unbonding to total delegation
+    TOTAL_UNBONDING.update(deps.storage, |n| -> Result<_, ContractError> {
+        sub_u128(n, amount)
+    })?;
+    TOTAL_DELEGATION.update(deps.storage, |n| -> Result<_, ContractError> {
+        add_u128(n, amount)
+    })?;
+
+    update_account_weight(deps.storage, prev_weight, account.weight()?)?;
+
+    amortize(
+        deps.storage,
+        &env.block,
+        seq_no,
+        Some(info.sender.to_owned()),
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "cancel_unbonding"),
+        attr("amount", amount.to_string()),
+        attr("delegation", account.delegation.to_string()),
+    ]))
+}
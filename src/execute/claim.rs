@@ -1,15 +1,19 @@
 use crate::{
     error::ContractError,
-    math::{add_u64, sub_u128},
+    math::{add_u128, sub_u128, sum_u128},
     msg::ClaimMsg,
     state::storage::{
-        ACCOUNTS, ACCOUNT_UNBONDINGS, BALANCES, SEQ_NO, STAKING_TOKEN, TOTAL_UNBONDING, X,
+        ACCOUNTS, BALANCES, SEQ_NO, STAKING_TOKEN, TOTAL_DELEGATION, TOTAL_UNBONDING,
+    },
+    sync::{
+        amortize, ensure_legacy_synced, load_delegation_scale, load_unbondings,
+        persist_sync_results, save_unbondings, sync_account, update_account_weight,
+        MAX_IMPLICIT_SYNC_EVENTS,
     },
-    sync::{amortize, persist_sync_results, sync_account},
 };
-use cosmwasm_std::{attr, Response, SubMsg, Uint128};
+use cosmwasm_std::{attr, Attribute, Response, SubMsg, Uint128};
 
-use super::Context;
+use super::{close_account_if_empty, compound::take_staking_revenue, Context};
 
 pub fn exec_claim(
     ctx: Context,
@@ -18,16 +22,55 @@ pub fn exec_claim(
     let Context { deps, info, env } = ctx;
     let seq_no = SEQ_NO.load(deps.storage)?;
 
-    // sync the account before processing claim
+    // Send claimed tokens to the given recipient or default to tx sender
+    let recipient = if let Some(recipient) = &params.recipient {
+        deps.api.addr_validate(recipient.as_str())?
+    } else {
+        info.sender.to_owned()
+    };
+
+    // The callback message is sent along with a single token, so that the
+    // recipient executes it once
+    if params.msg.is_some() && params.token.is_none() {
+        return Err(ContractError::ValidationError {
+            reason: "a token must be given to claim with a msg".to_owned(),
+        });
+    }
+
+    let mut account = ACCOUNTS.load(deps.storage, &info.sender)?;
+
+    // sync every token before processing claim, since claiming may change
+    // the account's weight
     let mut sync_states = sync_account(
         deps.storage,
-        deps.api,
         &info.sender,
-        &ACCOUNTS.load(deps.storage, &info.sender)?,
+        &account,
         seq_no,
-        Some(params.token.to_owned()),
-        true,
+        None,
+        Some(MAX_IMPLICIT_SYNC_EVENTS),
     )?;
+    ensure_legacy_synced(&sync_states, seq_no)?;
+
+    // Apply any reduction of delegation by payouts since the last rescale
+    let rescaled = account.rescale(load_delegation_scale(deps.storage)?)?;
+
+    let prev_weight = account.weight()?;
+
+    // Restake staking token revenue instead of paying it out
+    let mut compounded = Uint128::zero();
+    if account.auto_compound {
+        let staking_token = STAKING_TOKEN.load(deps.storage)?;
+        compounded = take_staking_revenue(deps.storage, &mut sync_states, &staking_token)?;
+        account.add_delegation(compounded)?;
+        TOTAL_DELEGATION.update(deps.storage, |n| -> Result<_, ContractError> {
+            add_u128(n, compounded)
+        })?;
+    }
+
+    let mut attrs: Vec<Attribute> = vec![
+        attr("action", "claim"),
+        attr("recipient", recipient.to_string()),
+    ];
 
     // accumulate transfer submsgs and reset sync amounts to 0
     let mut transfer_submsgs: Vec<SubMsg> = Vec::with_capacity(sync_states.len());
@@ -35,54 +78,87 @@ pub fn exec_claim(
     for (result, sync_state) in sync_states.iter_mut() {
         let token = &result.token;
         let token_key = token.to_key();
+        let is_claimed = match &params.token {
+            Some(claimed_token) => claimed_token.to_key() == token_key,
+            None => true,
+        };
+
+        if is_claimed && !sync_state.amount.is_zero() {
+            transfer_submsgs.push(token.transfer_or_send(
+                &recipient,
+                sync_state.amount,
+                params.msg.to_owned(),
+            )?);
+            attrs.push(attr("token", &token_key));
+            attrs.push(attr("amount", sync_state.amount.to_string()));
 
-        transfer_submsgs.push(token.transfer(&info.sender, sync_state.amount)?);
-
-        let updated_balance = BALANCES.update(
-            deps.storage,
-            &token_key,
-            |maybe_b| -> Result<_, ContractError> {
-                let mut b = maybe_b.unwrap(); // initialized in instantiate
-                b.amount = sub_u128(b.amount, sync_state.amount)?;
-                Ok(b)
-            },
-        )?;
-
-        sync_state.amount = Uint128::zero();
-        if updated_balance.amount.is_zero() {
-            BALANCES.remove(deps.storage, &token_key);
+            let updated_balance = BALANCES.update(
+                deps.storage,
+                &token_key,
+                |maybe_b| -> Result<_, ContractError> {
+                    let mut b = maybe_b.unwrap(); // nonzero synced amount implies a balance
+                    b.amount = sub_u128(b.amount, sync_state.amount)?;
+                    Ok(b)
+                },
+            )?;
+
+            sync_state.amount = Uint128::zero();
+            if updated_balance.amount.is_zero() {
+                BALANCES.remove(deps.storage, &token_key);
+            }
         }
 
         persist_sync_results(deps.storage, &info.sender, result, sync_state)?;
     }
 
-    // Increment trigger to indicate that next deposit should create new event.
-    X.update(deps.storage, |x| -> Result<_, ContractError> {
-        add_u64(x, 1u64)
-    })?;
+    if !compounded.is_zero() {
+        attrs.push(attr("compounded", compounded.to_string()));
+    }
+
+    // Release expired stake locks, dropping their boosted weight, and save
+    // any change in weight along with compounded or rescaled delegation.
+    if account.release_expired_locks(env.block.time) || !compounded.is_zero() || rescaled {
+        ACCOUNTS.save(deps.storage, &info.sender, &account)?;
+        update_account_weight(deps.storage, prev_weight, account.weight()?)?;
+    }
+
+    // Send the recipient every unbonding tranche that has matured in addition
+    // to everything else. It's transferred without the msg, which is only sent
+    // along with the claimed token.
+    let tranches = load_unbondings(deps.storage, &info.sender)?;
+    if !tranches.is_empty() {
+        let (matured, pending): (Vec<_>, Vec<_>) = tranches
+            .into_iter()
+            .partition(|t| env.block.time >= t.unbonds_at);
+
+        let unbonded_amount = sum_u128(matured.iter().map(|t| t.amount).collect())?;
 
-    // If the tx sender is unbonding and the unbonding timeout has ellapsed,
-    // then send them their unbonded amount in addition to everything else.
-    if let Some(unbonding) = ACCOUNT_UNBONDINGS.may_load(deps.storage, &info.sender)? {
-        if env.block.time >= unbonding.unbonds_at {
+        if !unbonded_amount.is_zero() {
             let staking_token = STAKING_TOKEN.load(deps.storage)?;
-            transfer_submsgs.push(staking_token.transfer(&info.sender, unbonding.amount)?);
-            ACCOUNT_UNBONDINGS.remove(deps.storage, &info.sender);
+            transfer_submsgs.push(staking_token.transfer(&recipient, unbonded_amount)?);
             TOTAL_UNBONDING.update(deps.storage, |n| -> Result<_, ContractError> {
-                sub_u128(n, unbonding.amount)
+                sub_u128(n, unbonded_amount)
             })?;
         }
+
+        save_unbondings(deps.storage, &info.sender, &pending)?;
+
+        attrs.push(attr("unbonded_amount", unbonded_amount.to_string()));
+        attrs.push(attr("n_unbonded_tranches", matured.len().to_string()));
     }
 
     amortize(
         deps.storage,
-        deps.api,
-        seq_no.into(),
-        Some(params.token),
-        Some(info.sender),
+        &env.block,
+        seq_no,
+        Some(info.sender.to_owned()),
     )?;
 
+    if close_account_if_empty(deps.storage, &info.sender)? {
+        attrs.push(attr("closed", "true"));
+    }
+
     Ok(Response::new()
-        .add_attributes(vec![attr("action", "claim")])
+        .add_attributes(attrs)
         .add_submessages(transfer_submsgs))
 }
@@ -0,0 +1,134 @@
+use crate::{
+    error::ContractError,
+    math::{add_u128, sub_u128},
+    state::{
+        models::AccountSyncState,
+        storage::{ACCOUNTS, BALANCES, SEQ_NO, STAKING_TOKEN, TOTAL_DELEGATION},
+    },
+    sync::{
+        amortize, load_delegation_scale, persist_sync_results, pin_legacy_weight, sync_account,
+        update_account_weight, TokenSyncResult, MAX_IMPLICIT_SYNC_EVENTS,
+    },
+    token::Token,
+};
+use cosmwasm_std::{attr, Response, Storage, Uint128};
+
+use super::Context;
+
+/// Restake the sender's synced staking token revenue as delegation
+pub fn exec_compound(ctx: Context) -> Result<Response, ContractError> {
+    let Context { deps, env, info } = ctx;
+    let seq_no = SEQ_NO.load(deps.storage)?;
+    let token = STAKING_TOKEN.load(deps.storage)?;
+
+    let mut account = ACCOUNTS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or_else(|| ContractError::NotAuthorized {
+            reason: "Account not found".to_owned(),
+        })?;
+
+    let mut results = sync_account(
+        deps.storage,
+        &info.sender,
+        &account,
+        seq_no,
+        None,
+        Some(MAX_IMPLICIT_SYNC_EVENTS),
+    )?;
+    pin_legacy_weight(deps.storage, &info.sender, &account, &results, seq_no)?;
+
+    let amount = take_staking_revenue(deps.storage, &mut results, &token)?;
+    if amount.is_zero() {
+        return Err(ContractError::ValidationError {
+            reason: "no staking token revenue to compound".to_owned(),
+        });
+    }
+
+    for (result, state) in results.iter() {
+        persist_sync_results(deps.storage, &info.sender, result, state)?;
+    }
+
+    // Apply any reduction of delegation by payouts since the last rescale
+    account.rescale(load_delegation_scale(deps.storage)?)?;
+
+    let prev_weight = account.weight()?;
+    account.add_delegation(amount)?;
+
+    ACCOUNTS.save(deps.storage, &info.sender, &account)?;
+
+    TOTAL_DELEGATION.update(deps.storage, |delegation| -> Result<_, ContractError> {
+        add_u128(delegation, amount)
+    })?;
+
+    update_account_weight(deps.storage, prev_weight, account.weight()?)?;
+
+    amortize(deps.storage, &env.block, seq_no, Some(info.sender))?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "compound"),
+        attr("amount", amount.to_string()),
+    ]))
+}
+
+/// Enable or disable automatic compounding of the sender's staking token
+/// revenue when they stake or claim.
+pub fn exec_set_auto_compound(
+    ctx: Context,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    let Context { deps, info, .. } = ctx;
+
+    ACCOUNTS.update(
+        deps.storage,
+        &info.sender,
+        |maybe_account| -> Result<_, ContractError> {
+            let mut account = maybe_account.ok_or_else(|| ContractError::NotAuthorized {
+                reason: "Account not found".to_owned(),
+            })?;
+            account.auto_compound = enabled;
+            Ok(account)
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "set_auto_compound"),
+        attr("enabled", enabled.to_string()),
+    ]))
+}
+
+/// Zero out the synced amount of staking token revenue in the given sync
+/// results, removing it from the house balance so that the caller can add it
+/// to the account's delegation. Returns the amount taken.
+pub fn take_staking_revenue(
+    store: &mut dyn Storage,
+    results: &mut [(TokenSyncResult, AccountSyncState)],
+    staking_token: &Token,
+) -> Result<Uint128, ContractError> {
+    let staking_token_key = staking_token.to_key();
+
+    for (result, sync_state) in results.iter_mut() {
+        if result.token.to_key() != staking_token_key || sync_state.amount.is_zero() {
+            continue;
+        }
+
+        let amount = sync_state.amount;
+        let updated_balance = BALANCES.update(
+            store,
+            &staking_token_key,
+            |maybe_b| -> Result<_, ContractError> {
+                let mut b = maybe_b.unwrap(); // nonzero synced amount implies a balance
+                b.amount = sub_u128(b.amount, amount)?;
+                Ok(b)
+            },
+        )?;
+
+        if updated_balance.amount.is_zero() {
+            BALANCES.remove(store, &staking_token_key);
+        }
+
+        sync_state.amount = Uint128::zero();
+        return Ok(amount);
+    }
+
+    Ok(Uint128::zero())
+}
@@ -0,0 +1,44 @@
+use crate::{
+    error::ContractError,
+    state::storage::{AMORTIZATION_CONFIG, SEQ_NO},
+    sync::{crank, DEFAULT_AMORTIZATION_CONFIG, MAX_CRANK_LIMIT, MAX_IMPLICIT_SYNC_EVENTS},
+};
+use cosmwasm_std::{attr, Response};
+
+use super::Context;
+
+/// Sync up to limit queued accounts, defaulting to the configured batch size.
+/// Anyone can crank.
+pub fn exec_crank(
+    ctx: Context,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let Context { deps, env, .. } = ctx;
+    let seq_no = SEQ_NO.load(deps.storage)?;
+
+    let limit = limit
+        .unwrap_or(
+            AMORTIZATION_CONFIG
+                .may_load(deps.storage)?
+                .unwrap_or(DEFAULT_AMORTIZATION_CONFIG)
+                .batch_size,
+        )
+        .min(MAX_CRANK_LIMIT);
+
+    // An explicit crank is the whole transaction, so it can afford as many
+    // legacy events per account as an implicit sync of that account would
+    let n_synced = crank(
+        deps.storage,
+        &env.block,
+        seq_no,
+        None,
+        limit,
+        limit.saturating_mul(MAX_IMPLICIT_SYNC_EVENTS),
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "crank"),
+        attr("limit", limit.to_string()),
+        attr("n_synced", n_synced.to_string()),
+    ]))
+}
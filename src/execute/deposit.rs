@@ -1,28 +1,49 @@
 use crate::{
     error::ContractError,
-    math::{add_u128, add_u32, add_u64, mul_ratio_u128, sub_u128, sum_u128},
+    math::{add_u128, add_u64, mul_ratio_u128, sub_u128, sum_u128},
     msg::DepositMsg,
     state::{
-        models::{BalanceEvent, DepositTotals, TaxRecipientBalance},
+        models::{DepositTotals, TaxRecipientBalance},
         storage::{
-            BALANCES, DEPOSITOR_TOTALS, DEPOSIT_AGG_TOTALS, N_ACCOUNTS, N_BALANCE_EVENTS,
-            N_DEPOSITS, REVENUE_TOKEN_KEYS, SEQ_NO, STAKING_TOKEN, TAX_RECIPIENT_CONFIGS,
-            TAX_RECIPIENT_TOTALS, TAX_TOTAL_BALANCES, TOTAL_DELEGATION, TOTAL_UNBONDING,
-            TS_BALANCE, X,
+            BALANCES, BURN_PCT, DEPOSITORS, DEPOSITOR_ALLOWLIST_ENABLED, DEPOSITOR_TOTALS,
+            DEPOSIT_AGG_TOTALS, DEPOSIT_CATEGORY_TOTALS, N_DEPOSITS, REVENUE_TOKEN_KEYS, SEQ_NO,
+            STAKING_TOKEN, TAX_RECIPIENT_CONFIGS, TAX_RECIPIENT_TOTALS, TAX_TOTAL_BALANCES,
+            TOTAL_DELEGATION, TOTAL_UNBONDING,
         },
     },
-    sync::amortize,
-    token::TokenAmount,
+    sync::{amortize, distribute_revenue},
+    token::{Token, TokenAmount},
 };
 use cosmwasm_std::{
     attr, Addr, Empty, Order, QuerierWrapper, Response, StdResult, Storage, SubMsg, Uint128, Uint64,
 };
 
-use super::Context;
+use super::{verify_funds, Context};
 
 pub fn exec_deposit(
     ctx: Context,
     params: DepositMsg,
+) -> Result<Response, ContractError> {
+    // CW20 revenue can only be deposited through a CW20 Send, which proves
+    // that the tokens were actually received.
+    if let Token::Address(_) = params.token {
+        return Err(ContractError::NotAuthorized {
+            reason: "CW20 tokens must be deposited via Receive".to_owned(),
+        });
+    }
+
+    // Ensure the native token was sent and refund any overpayment
+    let excess = verify_funds(&ctx.info.funds, &params.token, params.amount)?;
+
+    process_deposit(ctx, params, excess)
+}
+
+/// Distribute a deposit already received by the contract to tax recipients
+/// and stakers. Any excess amount received is refunded to the sender.
+pub fn process_deposit(
+    ctx: Context,
+    params: DepositMsg,
+    excess: Uint128,
 ) -> Result<Response, ContractError> {
     let Context { deps, env, info } = ctx;
     let seq_no = SEQ_NO.load(deps.storage)?;
@@ -30,6 +51,13 @@ pub fn exec_deposit(
     let mut resp = Response::new().add_attributes(vec![attr("action", "deposit")]);
     let mut params = params;
 
+    // Refund overpayment. It's excluded from the untracked balance below.
+    if !excess.is_zero() {
+        resp = resp
+            .add_attribute("refund", excess.to_string())
+            .add_submessage(params.token.transfer(&info.sender, excess)?);
+    }
+
     // Only allow whitelisted token types
     if !REVENUE_TOKEN_KEYS.has(deps.storage, &token_key) {
         return Err(ContractError::NotAuthorized {
@@ -37,28 +65,49 @@ pub fn exec_deposit(
         });
     }
 
+    // Only allow allowlisted depositors if the allowlist is enabled
+    let depositor = DEPOSITORS.may_load(deps.storage, &info.sender)?;
+    if depositor.is_none()
+        && DEPOSITOR_ALLOWLIST_ENABLED
+            .may_load(deps.storage)?
+            .unwrap_or_default()
+    {
+        return Err(ContractError::NotAuthorized {
+            reason: "depositor not allowlisted".to_owned(),
+        });
+    }
+
     // Update the depostor's totals BEFORE syncging untracked balance which
     // changing params.amount in place.
     update_depositor_totals(deps.storage, &info.sender, &token_key, params.amount)?;
 
+    if let Some(depositor) = depositor {
+        update_category_totals(
+            deps.storage,
+            depositor.category.to_key(),
+            &token_key,
+            params.amount,
+        )?;
+    }
+
     // Sync any untracked balance with the tracked balance and add the
     // difference to the total amount to be deposited below
     params.amount = add_u128(
-        sync_untracked_balance(deps.storage, deps.querier, &env.contract.address, &params)?,
+        sync_untracked_balance(
+            deps.storage,
+            deps.querier,
+            &env.contract.address,
+            &params,
+            excess,
+        )?,
         params.amount,
     )?;
 
     // Perform deposit and return submsg to transfer any tax to tax recipient
-    let fee_transfer_submsgs = deposit(deps.storage, params.to_owned(), seq_no)?;
+    let fee_transfer_submsgs = deposit(deps.storage, &env.contract.address, params.to_owned())?;
     resp = resp.add_submessages(fee_transfer_submsgs);
 
-    amortize(
-        deps.storage,
-        deps.api,
-        seq_no.into(),
-        Some(params.token),
-        None,
-    )?;
+    amortize(deps.storage, &env.block, seq_no, None)?;
 
     Ok(resp)
 }
@@ -68,14 +117,15 @@ fn sync_untracked_balance(
     querier: QuerierWrapper<Empty>,
     contract_addr: &Addr,
     params: &DepositMsg,
+    excess: Uint128,
 ) -> Result<Uint128, ContractError> {
     let DepositMsg { amount, token } = params.to_owned();
-    let contract_balance = token.query_balance(querier, contract_addr)?;
+    let contract_balance = sub_u128(token.query_balance(querier, contract_addr)?, excess)?;
     let token_key = &token.to_key();
 
     let tracked_balance = BALANCES
         .may_load(store, token_key)?
-        .and_then(|b| Some(b.amount))
+        .map(|b| b.amount)
         .unwrap_or_default();
 
     let untracked_balance = sub_u128(
@@ -104,112 +154,41 @@ fn sync_untracked_balance(
     Ok(Uint128::zero())
 }
 
-fn deposit(
+/// Burn, tax and distribute deposited revenue to stakers, recording it in the
+/// deposit stats. Returns submsgs that burn tokens and send autosend taxes.
+pub fn deposit(
     store: &mut dyn Storage,
+    contract_addr: &Addr,
     params: DepositMsg,
-    seq_no: Uint64,
 ) -> Result<Vec<SubMsg>, ContractError> {
-    let DepositMsg {
-        amount: revenue,
-        token,
-    } = params;
+    let DepositMsg { amount, token } = params;
 
     let token_key = token.to_key();
 
-    // Send or allocate taxes to fee recipients
     let mut transfer_fee_submsgs: Vec<SubMsg> = Vec::with_capacity(1);
-    let mut tax_revenue = Uint128::zero();
-
-    for result in TAX_RECIPIENT_CONFIGS
-        .range(store, None, None, Order::Ascending)
-        .collect::<Vec<StdResult<_>>>()
-    {
-        let (tax_recipient_addr, info) = result?;
-        let tax_delta = mul_ratio_u128(revenue, info.pct, 1_000_000u128)?;
-
-        tax_revenue = add_u128(tax_revenue, tax_delta)?;
 
-        if info.autosend {
-            transfer_fee_submsgs.push(token.transfer(&tax_recipient_addr, tax_delta)?);
-        } else {
-            // Increment total amoutn held for taxes with respect to this token type
-            TAX_TOTAL_BALANCES.update(store, &token_key, |n| -> Result<_, ContractError> {
-                add_u128(n.unwrap_or_default(), tax_delta)
-            })?;
-        }
-
-        TAX_RECIPIENT_TOTALS.update(
-            store,
-            (&tax_recipient_addr, &token_key),
-            |maybe_totals| -> Result<_, ContractError> {
-                let mut totals = maybe_totals.unwrap_or_else(|| TaxRecipientBalance {
-                    balance: Uint128::zero(),
-                    total: Uint128::zero(),
-                });
-                totals.total = add_u128(totals.total, tax_delta)?;
-                if !info.autosend {
-                    totals.balance = add_u128(totals.balance, tax_delta)?;
-                }
-                Ok(totals)
-            },
-        )?;
+    // Burn the configured share of revenue before computing taxes
+    let burn_amount = mul_ratio_u128(
+        amount,
+        BURN_PCT.may_load(store)?.unwrap_or_default(),
+        1_000_000u128,
+    )?;
+    if !burn_amount.is_zero() {
+        transfer_fee_submsgs.push(token.burn(contract_addr, burn_amount)?);
     }
 
-    // Compute house revenue after taxes
-    let staking_revenue = sub_u128(revenue, tax_revenue)?;
-
-    // Load total delegation amount across all delegators at this moment
-    let total_delegation = TOTAL_DELEGATION.load(store)?;
-    let x = X.load(store)?;
-
-    // Insert revenue time series
-    let n_accounts = N_ACCOUNTS.load(store)?;
+    transfer_fee_submsgs.extend(distribute(
+        store,
+        &token,
+        sub_u128(amount, burn_amount)?,
+        true,
+    )?);
 
     //Increment n_deposits and return pre-incremented count
     N_DEPOSITS.update(store, &token_key, |maybe_n| -> Result<_, ContractError> {
         add_u64(maybe_n.unwrap_or_default(), 1u64)
     })?;
 
-    let mut insert_new_event = true;
-
-    // If applicable, update the most recent "balance" time series entry.
-    // otherwise, create a new one below.
-    if N_BALANCE_EVENTS
-        .may_load(store, &token_key)?
-        .unwrap_or_default()
-        > 0
-    {
-        let key = (&token_key, seq_no.u64() - 1);
-        let mut existing_event = TS_BALANCE.load(store, key)?;
-        if existing_event.x == x {
-            existing_event.delta = add_u128(existing_event.delta, revenue)?;
-            TS_BALANCE.save(store, key, &existing_event)?;
-            insert_new_event = false;
-        }
-    }
-
-    // Create a new "balance" timeseries entry
-    if insert_new_event {
-        // NOTE: The time series key uses the pre-incremented seq_no
-        SEQ_NO.save(store, &add_u64(seq_no, 1u64)?)?;
-
-        TS_BALANCE.save(
-            store,
-            (&token_key, seq_no.u64()),
-            &BalanceEvent {
-                delta: staking_revenue,
-                total: total_delegation,
-                ref_count: n_accounts,
-                n_accounts,
-                x,
-            },
-        )?;
-
-        N_BALANCE_EVENTS.update(store, &token_key, |maybe_n| -> Result<_, ContractError> {
-            add_u32(maybe_n.unwrap_or_default(), 1)
-        })?;
-    }
-
     // Increment running total historical deposit amount
     DEPOSIT_AGG_TOTALS.update(
         store,
@@ -219,12 +198,74 @@ fn deposit(
                 amount: Uint128::zero(),
                 n: Uint64::zero(),
             });
-            totals.amount = add_u128(totals.amount, params.amount)?;
+            totals.amount = add_u128(totals.amount, amount)?;
             totals.n = add_u64(totals.n, 1u64)?;
             Ok(totals)
         },
     )?;
 
+    Ok(transfer_fee_submsgs)
+}
+
+/// Tax revenue held by the contract and distribute the rest to stakers,
+/// without burning any of it or counting it as a deposit. Returns submsgs that
+/// send autosend taxes. Taxes are skipped if not `taxed`.
+pub fn distribute(
+    store: &mut dyn Storage,
+    token: &Token,
+    revenue: Uint128,
+    taxed: bool,
+) -> Result<Vec<SubMsg>, ContractError> {
+    let token_key = token.to_key();
+
+    let mut transfer_fee_submsgs: Vec<SubMsg> = Vec::with_capacity(1);
+
+    // Send or allocate taxes to fee recipients
+    let mut tax_revenue = Uint128::zero();
+
+    if taxed {
+        for result in TAX_RECIPIENT_CONFIGS
+            .range(store, None, None, Order::Ascending)
+            .collect::<Vec<StdResult<_>>>()
+        {
+            let (tax_recipient_addr, info) = result?;
+            let tax_delta = mul_ratio_u128(revenue, info.pct, 1_000_000u128)?;
+
+            tax_revenue = add_u128(tax_revenue, tax_delta)?;
+
+            if info.autosend {
+                transfer_fee_submsgs.push(token.transfer(&tax_recipient_addr, tax_delta)?);
+            } else {
+                // Increment total amoutn held for taxes with respect to this token type
+                TAX_TOTAL_BALANCES.update(store, &token_key, |n| -> Result<_, ContractError> {
+                    add_u128(n.unwrap_or_default(), tax_delta)
+                })?;
+            }
+
+            TAX_RECIPIENT_TOTALS.update(
+                store,
+                (&tax_recipient_addr, &token_key),
+                |maybe_totals| -> Result<_, ContractError> {
+                    let mut totals = maybe_totals.unwrap_or_else(|| TaxRecipientBalance {
+                        balance: Uint128::zero(),
+                        total: Uint128::zero(),
+                    });
+                    totals.total = add_u128(totals.total, tax_delta)?;
+                    if !info.autosend {
+                        totals.balance = add_u128(totals.balance, tax_delta)?;
+                    }
+                    Ok(totals)
+                },
+            )?;
+        }
+    }
+
+    // Compute house revenue after taxes
+    let staking_revenue = sub_u128(revenue, tax_revenue)?;
+
+    // Distribute house revenue to stakers in proportion to their weight
+    distribute_revenue(store, &token_key, staking_revenue)?;
+
     // Increment global house revenue for this token type
     BALANCES.update(store, &token_key, |maybe_ta| -> Result<_, ContractError> {
         Ok(if let Some(mut ta) = maybe_ta {
@@ -263,3 +304,26 @@ fn update_depositor_totals(
 
     Ok(())
 }
+
+fn update_category_totals(
+    store: &mut dyn Storage,
+    category: &str,
+    token_key: &String,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    DEPOSIT_CATEGORY_TOTALS.update(
+        store,
+        (category, token_key),
+        |maybe_totals| -> Result<_, ContractError> {
+            let mut totals = maybe_totals.unwrap_or_else(|| DepositTotals {
+                amount: Uint128::zero(),
+                n: Uint64::zero(),
+            });
+            totals.amount = add_u128(totals.amount, amount)?;
+            totals.n = add_u64(totals.n, 1u64)?;
+            Ok(totals)
+        },
+    )?;
+
+    Ok(())
+}
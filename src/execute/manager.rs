@@ -0,0 +1,115 @@
+use crate::{
+    error::ContractError,
+    state::{
+        models::PendingManager,
+        storage::{MANAGED_BY, PENDING_MANAGER},
+    },
+};
+use cosmwasm_std::{attr, Addr, Response, Timestamp};
+
+use super::{ensure_manager, Context};
+
+/// Propose a new manager, who must accept the proposal before it takes effect.
+/// Any existing proposal is replaced.
+pub fn exec_propose_manager(
+    ctx: Context,
+    address: Addr,
+    expires_at: Option<Timestamp>,
+) -> Result<Response, ContractError> {
+    let Context { deps, env, info } = ctx;
+
+    ensure_manager(deps.storage, &info.sender)?;
+
+    let address = deps.api.addr_validate(address.as_str())?;
+
+    if address == info.sender {
+        return Err(ContractError::ValidationError {
+            reason: "proposed manager is already the manager".to_owned(),
+        });
+    }
+
+    if let Some(expires_at) = expires_at {
+        if expires_at <= env.block.time {
+            return Err(ContractError::ValidationError {
+                reason: "expiration must be in the future".to_owned(),
+            });
+        }
+    }
+
+    PENDING_MANAGER.save(
+        deps.storage,
+        &PendingManager {
+            address: address.to_owned(),
+            proposed_at: env.block.time,
+            expires_at,
+        },
+    )?;
+
+    let mut attrs = vec![
+        attr("action", "propose_manager"),
+        attr("address", address.to_string()),
+    ];
+    if let Some(expires_at) = expires_at {
+        attrs.push(attr("expires_at", expires_at.nanos().to_string()));
+    }
+
+    Ok(Response::new().add_attributes(attrs))
+}
+
+/// Accept a pending manager proposal as the proposed address
+pub fn exec_accept_manager(ctx: Context) -> Result<Response, ContractError> {
+    let Context { deps, env, info } = ctx;
+
+    let pending =
+        PENDING_MANAGER
+            .may_load(deps.storage)?
+            .ok_or_else(|| ContractError::NotAuthorized {
+                reason: "no pending manager proposal".to_owned(),
+            })?;
+
+    if info.sender != pending.address {
+        return Err(ContractError::NotAuthorized {
+            reason: "only the proposed manager can accept".to_owned(),
+        });
+    }
+
+    if let Some(expires_at) = pending.expires_at {
+        if env.block.time >= expires_at {
+            return Err(ContractError::NotAuthorized {
+                reason: "manager proposal expired".to_owned(),
+            });
+        }
+    }
+
+    let prev_manager = MANAGED_BY.load(deps.storage)?;
+
+    MANAGED_BY.save(deps.storage, &pending.address)?;
+    PENDING_MANAGER.remove(deps.storage);
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "accept_manager"),
+        attr("prev_manager", prev_manager.to_string()),
+        attr("manager", pending.address.to_string()),
+    ]))
+}
+
+/// Cancel the pending manager proposal
+pub fn exec_cancel_manager_proposal(ctx: Context) -> Result<Response, ContractError> {
+    let Context { deps, info, .. } = ctx;
+
+    ensure_manager(deps.storage, &info.sender)?;
+
+    let pending =
+        PENDING_MANAGER
+            .may_load(deps.storage)?
+            .ok_or_else(|| ContractError::ValidationError {
+                reason: "no pending manager proposal".to_owned(),
+            })?;
+
+    PENDING_MANAGER.remove(deps.storage);
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "cancel_manager_proposal"),
+        attr("address", pending.address.to_string()),
+    ]))
+}
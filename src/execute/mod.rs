@@ -1,12 +1,172 @@
+pub mod admin;
+pub mod cancel_unbonding;
 pub mod claim;
+pub mod compound;
+pub mod crank;
 pub mod deposit;
+pub mod manager;
+pub mod payout;
+pub mod receive;
+pub mod slash;
 pub mod stake;
+pub mod sync_account;
+pub mod taxes;
 pub mod unstake;
 
-use cosmwasm_std::{DepsMut, Env, MessageInfo};
+use cosmwasm_std::{Addr, Coin, DepsMut, Env, MessageInfo, Order, StdResult, Storage, Uint128};
+
+use crate::{
+    error::ContractError,
+    math::{sub_u128, sub_u32},
+    state::storage::{
+        ACCOUNTS, ACCOUNT_SYNC_INFOS, CLOSED_QUEUED_ACCOUNTS, MANAGED_BY, N_ACCOUNTS, SEQ_NO,
+        TS_STAKE,
+    },
+    sync::{load_reward_tokens, load_sync_state, load_unbondings, save_unbondings},
+    token::Token,
+};
 
 pub struct Context<'a> {
     pub deps: DepsMut<'a>,
     pub env: Env,
     pub info: MessageInfo,
 }
+
+/// Ensure that the given address is the house manager
+pub fn ensure_manager(
+    store: &dyn Storage,
+    sender: &Addr,
+) -> Result<(), ContractError> {
+    if *sender != MANAGED_BY.load(store)? {
+        return Err(ContractError::NotAuthorized {
+            reason: "only the contract manager can perform this action".to_owned(),
+        });
+    }
+    Ok(())
+}
+
+/// Verify that the given funds contain at least the expected amount of the
+/// native token and no other denoms. Returns the overpaid amount, which the
+/// caller should refund.
+pub fn verify_funds(
+    funds: &[Coin],
+    token: &Token,
+    amount: Uint128,
+) -> Result<Uint128, ContractError> {
+    if amount.is_zero() {
+        return Err(ContractError::ValidationError {
+            reason: "amount must be greater than zero".to_owned(),
+        });
+    }
+    if !token.has_in_funds(funds, None) {
+        return Err(ContractError::ValidationError {
+            reason: format!("expected {} {:?} in funds", amount, token),
+        });
+    }
+    if funds.len() > 1 {
+        return Err(ContractError::ValidationError {
+            reason: "unexpected denoms in funds".to_owned(),
+        });
+    }
+    if token.has_in_funds(funds, Some(amount)) {
+        return Ok(Uint128::zero());
+    }
+
+    let received = funds[0].amount;
+    if received < amount {
+        return Err(ContractError::ValidationError {
+            reason: format!(
+                "insufficient funds: expected {}, received {}",
+                amount, received
+            ),
+        });
+    }
+
+    sub_u128(received, amount)
+}
+
+/// Ensure optional text is nonempty and within the given max length
+pub fn validate_text(
+    field: &str,
+    value: &Option<String>,
+    max_len: usize,
+) -> Result<(), ContractError> {
+    if let Some(value) = value {
+        if value.trim().is_empty() || value.len() > max_len {
+            return Err(ContractError::ValidationError {
+                reason: format!("{} must be nonempty and at most {} bytes", field, max_len),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Ensure the contract can burn the token, as required of every revenue token
+/// while deposits are partially burned
+pub fn ensure_burnable(
+    token: &Token,
+    contract_addr: &Addr,
+) -> Result<(), ContractError> {
+    if !token.is_burnable(contract_addr) {
+        return Err(ContractError::ValidationError {
+            reason: format!(
+                "token {} cannot be burned by the contract, which must be its tokenfactory admin",
+                token.to_key()
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Close the account if it has no delegation, unbonding or unclaimed revenue,
+/// deleting its state and decrementing N_ACCOUNTS. Since it must have synced
+/// every legacy event, it no longer holds references to any of them. Its
+/// address is dropped from the amortization queue the next time it's cranked.
+/// Returns true if the account was closed.
+pub fn close_account_if_empty(
+    store: &mut dyn Storage,
+    address: &Addr,
+) -> Result<bool, ContractError> {
+    let account = if let Some(account) = ACCOUNTS.may_load(store, address)? {
+        account
+    } else {
+        return Ok(false);
+    };
+
+    if !account.delegation.is_zero() || !load_unbondings(store, address)?.is_empty() {
+        return Ok(false);
+    }
+
+    let seq_no = SEQ_NO.load(store)?;
+    for token in load_reward_tokens(store)?.iter() {
+        let sync_state = load_sync_state(store, address, &account, token)?;
+        if !sync_state.amount.is_zero() || sync_state.seq_no < seq_no {
+            return Ok(false);
+        }
+    }
+
+    for token_key in ACCOUNT_SYNC_INFOS
+        .prefix(address)
+        .keys(store, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?
+    {
+        ACCOUNT_SYNC_INFOS.remove(store, (address, &token_key));
+    }
+
+    for key in TS_STAKE
+        .prefix(address)
+        .keys(store, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?
+    {
+        TS_STAKE.remove(store, (address, key));
+    }
+
+    // Drop tranches reduced to nothing by slashing
+    save_unbondings(store, address, &vec![])?;
+
+    ACCOUNTS.remove(store, address)?;
+    CLOSED_QUEUED_ACCOUNTS.save(store, address, &0)?;
+    N_ACCOUNTS.update(store, |n| -> Result<_, ContractError> { sub_u32(n, 1) })?;
+
+    Ok(true)
+}
@@ -0,0 +1,102 @@
+use crate::{
+    error::ContractError,
+    math::{add_u128, mul_ratio_u128},
+    state::{
+        models::PayoutWindow,
+        storage::{GAMES, PAYOUT_CONFIG, PAYOUT_WINDOW, SEQ_NO, STAKING_TOKEN, TOTAL_DELEGATION},
+    },
+    sync::{amortize, reduce_delegation},
+    token::Token,
+};
+use cosmwasm_std::{attr, Addr, Response, Uint128};
+
+use super::Context;
+
+/// Pay the recipient from staked delegation on behalf of an authorized game.
+/// The loss is shared by all stakers in proportion to their delegation.
+pub fn exec_request_payout(
+    ctx: Context,
+    recipient: Addr,
+    token: Token,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let Context { deps, env, info } = ctx;
+
+    if !GAMES.has(deps.storage, &info.sender) {
+        return Err(ContractError::NotAuthorized {
+            reason: "only authorized games can request payouts".to_owned(),
+        });
+    }
+
+    let config =
+        PAYOUT_CONFIG
+            .may_load(deps.storage)?
+            .ok_or_else(|| ContractError::NotAuthorized {
+                reason: "payouts are disabled".to_owned(),
+            })?;
+
+    deps.api.addr_validate(recipient.as_str())?;
+
+    // Payouts are drawn from delegation, so only the staking token is paid
+    let staking_token = STAKING_TOKEN.load(deps.storage)?;
+    if token.to_key() != staking_token.to_key() {
+        return Err(ContractError::ValidationError {
+            reason: "payouts must be in the staking token".to_owned(),
+        });
+    }
+
+    if amount.is_zero() {
+        return Err(ContractError::ValidationError {
+            reason: "payout amount must be nonzero".to_owned(),
+        });
+    }
+
+    let total_delegation = TOTAL_DELEGATION.load(deps.storage)?;
+    let max_payout = mul_ratio_u128(total_delegation, config.max_payout_pct, 1_000_000u128)?;
+
+    if amount > max_payout {
+        return Err(ContractError::ValidationError {
+            reason: format!("payout exceeds max of {}", max_payout),
+        });
+    }
+
+    // Start a new window if the current one has elapsed
+    let mut window = PAYOUT_WINDOW
+        .may_load(deps.storage)?
+        .filter(|w| env.block.time < w.started_at.plus_seconds(config.window_seconds.u64()))
+        .unwrap_or(PayoutWindow {
+            started_at: env.block.time,
+            amount: Uint128::zero(),
+        });
+
+    let max_window_payout = mul_ratio_u128(total_delegation, config.max_window_pct, 1_000_000u128)?;
+
+    window.amount = add_u128(window.amount, amount)?;
+    if window.amount > max_window_payout {
+        return Err(ContractError::ValidationError {
+            reason: format!(
+                "payouts exceed max of {} per {} seconds",
+                max_window_payout, config.window_seconds
+            ),
+        });
+    }
+
+    PAYOUT_WINDOW.save(deps.storage, &window)?;
+
+    let scale = reduce_delegation(deps.storage, amount)?;
+    let seq_no = SEQ_NO.load(deps.storage)?;
+
+    amortize(deps.storage, &env.block, seq_no, None)?;
+
+    Ok(Response::new()
+        .add_attributes(vec![
+            attr("action", "request_payout"),
+            attr("game", info.sender.to_string()),
+            attr("recipient", recipient.to_string()),
+            attr("token", token.to_key()),
+            attr("amount", amount.to_string()),
+            attr("scale", scale.value.to_string()),
+            attr("scale_exponent", scale.exponent.to_string()),
+        ])
+        .add_submessage(staking_token.transfer(&recipient, amount)?))
+}
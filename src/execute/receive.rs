@@ -0,0 +1,64 @@
+use crate::{
+    error::ContractError,
+    msg::{DepositMsg, ReceiveHookMsg, StakeMsg},
+    state::storage::{REVENUE_TOKEN_KEYS, STAKING_TOKEN},
+    token::Token,
+};
+use cosmwasm_std::{from_json, MessageInfo, Response, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+use super::{deposit::process_deposit, stake::process_stake, Context};
+
+pub fn exec_receive(
+    ctx: Context,
+    params: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let Context { deps, env, info } = ctx;
+    let Cw20ReceiveMsg {
+        sender,
+        amount,
+        msg,
+    } = params;
+
+    // The CW20 contract that sent the tokens is the tx sender
+    let token = Token::Address(info.sender);
+
+    // Process the hook on behalf of the account that sent the CW20 tokens
+    let info = MessageInfo {
+        sender: deps.api.addr_validate(&sender)?,
+        funds: vec![],
+    };
+
+    match from_json::<ReceiveHookMsg>(&msg)? {
+        ReceiveHookMsg::Stake {
+            address,
+            lock_seconds,
+        } => {
+            if token != STAKING_TOKEN.load(deps.storage)? {
+                return Err(ContractError::NotAuthorized {
+                    reason: "token is not the staking token".to_owned(),
+                });
+            }
+            process_stake(
+                Context { deps, env, info },
+                StakeMsg {
+                    amount,
+                    address,
+                    lock_seconds,
+                },
+            )
+        },
+        ReceiveHookMsg::Deposit {} => {
+            if !REVENUE_TOKEN_KEYS.has(deps.storage, &token.to_key()) {
+                return Err(ContractError::NotAuthorized {
+                    reason: "token type not accepted in deposits".to_owned(),
+                });
+            }
+            process_deposit(
+                Context { deps, env, info },
+                DepositMsg { amount, token },
+                Uint128::zero(),
+            )
+        },
+    }
+}
@@ -0,0 +1,200 @@
+use crate::{
+    error::ContractError,
+    math::{add_u128, mul_ratio_u128, sub_u128},
+    state::storage::{
+        ACCOUNTS, SEQ_NO, SLASH_RECIPIENT, STAKING_TOKEN, TOTAL_DELEGATION, TOTAL_UNBONDING,
+    },
+    sync::{
+        amortize, load_delegation_scale, load_unbondings, persist_sync_results, pin_legacy_weight,
+        reduce_delegation, reduce_unbonding, save_unbondings, sync_account, update_account_weight,
+        MAX_IMPLICIT_SYNC_EVENTS,
+    },
+    token::Token,
+};
+use cosmwasm_std::{attr, Addr, Response, Storage, SubMsg, Uint128};
+
+use super::{close_account_if_empty, Context};
+
+pub fn exec_set_slash_recipient(
+    ctx: Context,
+    address: Option<Addr>,
+) -> Result<Response, ContractError> {
+    let Context { deps, env, .. } = ctx;
+
+    let mut attrs = vec![attr("action", "set_slash_recipient")];
+
+    if let Some(address) = address {
+        deps.api.addr_validate(address.as_str())?;
+        SLASH_RECIPIENT.save(deps.storage, &address)?;
+        attrs.push(attr("address", address.to_string()));
+    } else {
+        ensure_slash_burnable(&STAKING_TOKEN.load(deps.storage)?, &env.contract.address)?;
+        SLASH_RECIPIENT.remove(deps.storage);
+        attrs.push(attr("burn", "true"));
+    }
+
+    Ok(Response::new().add_attributes(attrs))
+}
+
+/// Slash every account's delegation and unbonding tranches by the given rate
+/// out of 1000000. Accounts apply the reduction lazily.
+pub fn exec_slash(
+    ctx: Context,
+    ratio: Uint128,
+) -> Result<Response, ContractError> {
+    let Context { deps, env, .. } = ctx;
+    let seq_no = SEQ_NO.load(deps.storage)?;
+
+    if ratio.is_zero() || ratio >= Uint128::from(1_000_000u128) {
+        return Err(ContractError::ValidationError {
+            reason: "ratio must be greater than zero and less than 1000000 or 100%".to_owned(),
+        });
+    }
+
+    let delegation_amount =
+        mul_ratio_u128(TOTAL_DELEGATION.load(deps.storage)?, ratio, 1_000_000u128)?;
+    if !delegation_amount.is_zero() {
+        reduce_delegation(deps.storage, delegation_amount)?;
+    }
+
+    let unbonding_amount = reduce_unbonding(deps.storage, ratio)?;
+    let amount = add_u128(delegation_amount, unbonding_amount)?;
+
+    amortize(deps.storage, &env.block, seq_no, None)?;
+
+    let mut resp = Response::new().add_attributes(vec![
+        attr("action", "slash"),
+        attr("ratio", ratio.to_string()),
+        attr("delegation_amount", delegation_amount.to_string()),
+        attr("unbonding_amount", unbonding_amount.to_string()),
+    ]);
+
+    if !amount.is_zero() {
+        resp = resp.add_submessage(slash_submsg(deps.storage, &env.contract.address, amount)?);
+    }
+
+    Ok(resp)
+}
+
+/// Slash the given amount from an account's delegation along with the same
+/// proportion of each of its unbonding tranches.
+pub fn exec_slash_account(
+    ctx: Context,
+    address: Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let Context { deps, env, .. } = ctx;
+    let seq_no = SEQ_NO.load(deps.storage)?;
+
+    let mut account =
+        ACCOUNTS
+            .may_load(deps.storage, &address)?
+            .ok_or_else(|| ContractError::NotAuthorized {
+                reason: "Account not found".to_owned(),
+            })?;
+
+    // Sync the account before changing its weight
+    let results = sync_account(
+        deps.storage,
+        &address,
+        &account,
+        seq_no,
+        None,
+        Some(MAX_IMPLICIT_SYNC_EVENTS),
+    )?;
+    pin_legacy_weight(deps.storage, &address, &account, &results, seq_no)?;
+
+    for (result, state) in results.iter() {
+        persist_sync_results(deps.storage, &address, result, state)?;
+    }
+
+    // Apply any reduction of delegation by payouts since the last rescale
+    account.rescale(load_delegation_scale(deps.storage)?)?;
+
+    if amount.is_zero() || amount > account.delegation {
+        return Err(ContractError::ValidationError {
+            reason: format!(
+                "amount must be greater than zero and at most {}",
+                account.delegation
+            ),
+        });
+    }
+
+    // Slash unbonding tranches in proportion to the delegation slashed
+    let mut tranches = load_unbondings(deps.storage, &address)?;
+    let mut unbonding_amount = Uint128::zero();
+    for tranche in tranches.iter_mut() {
+        let tranche_amount = mul_ratio_u128(tranche.amount, amount, account.delegation)?;
+        tranche.amount = sub_u128(tranche.amount, tranche_amount)?;
+        unbonding_amount = add_u128(unbonding_amount, tranche_amount)?;
+    }
+    tranches.retain(|t| !t.amount.is_zero());
+    save_unbondings(deps.storage, &address, &tranches)?;
+
+    let prev_weight = account.weight()?;
+
+    account.slash(amount)?;
+
+    ACCOUNTS.save(deps.storage, &address, &account)?;
+
+    TOTAL_DELEGATION.update(deps.storage, |n| -> Result<_, ContractError> {
+        sub_u128(n, amount)
+    })?;
+    TOTAL_UNBONDING.update(deps.storage, |n| -> Result<_, ContractError> {
+        sub_u128(n, unbonding_amount)
+    })?;
+
+    update_account_weight(deps.storage, prev_weight, account.weight()?)?;
+
+    amortize(deps.storage, &env.block, seq_no, Some(address.to_owned()))?;
+
+    let mut attrs = vec![
+        attr("action", "slash_account"),
+        attr("address", address.to_string()),
+        attr("delegation_amount", amount.to_string()),
+        attr("unbonding_amount", unbonding_amount.to_string()),
+    ];
+
+    if close_account_if_empty(deps.storage, &address)? {
+        attrs.push(attr("closed", "true"));
+    }
+
+    Ok(Response::new()
+        .add_attributes(attrs)
+        .add_submessage(slash_submsg(
+            deps.storage,
+            &env.contract.address,
+            add_u128(amount, unbonding_amount)?,
+        )?))
+}
+
+/// Send slashed stake to the slash recipient or else burn it
+fn slash_submsg(
+    store: &dyn Storage,
+    contract_addr: &Addr,
+    amount: Uint128,
+) -> Result<SubMsg, ContractError> {
+    let token = STAKING_TOKEN.load(store)?;
+    Ok(if let Some(recipient) = SLASH_RECIPIENT.may_load(store)? {
+        token.transfer(&recipient, amount)?
+    } else {
+        ensure_slash_burnable(&token, contract_addr)?;
+        token.burn(contract_addr, amount)?
+    })
+}
+
+/// Ensure that slashed stake can be burned in the absence of a slash recipient
+fn ensure_slash_burnable(
+    token: &Token,
+    contract_addr: &Addr,
+) -> Result<(), ContractError> {
+    if !token.is_burnable(contract_addr) {
+        return Err(ContractError::ValidationError {
+            reason: format!(
+                "a slash recipient is required, since staking token {} cannot be burned by the contract",
+                token.to_key()
+            ),
+        });
+    }
+    Ok(())
+}
@@ -1,23 +1,58 @@
 use crate::{
     error::ContractError,
-    math::{add_u128, add_u32, add_u64},
+    math::{add_u128, add_u32},
     msg::StakeMsg,
     state::{
-        models::{Account, StakingEvent},
+        models::{Account, StakeLock},
         storage::{
-            ACCOUNTS, AMORTIZATION_QUEUE, N_ACCOUNTS, SEQ_NO, STAKING_TOKEN, TOTAL_DELEGATION,
-            TS_STAKE, X,
+            ACCOUNTS, AMORTIZATION_QUEUE, CLOSED_QUEUED_ACCOUNTS, LOCK_TIERS, MIN_STAKE_INCREMENT,
+            N_ACCOUNTS, SEQ_NO, STAKING_TOKEN, TOTAL_DELEGATION,
         },
     },
-    sync::{amortize, persist_sync_results, sync_account},
+    sync::{
+        amortize, load_delegation_scale, persist_sync_results, pin_legacy_weight, sync_account,
+        update_account_weight, MAX_IMPLICIT_SYNC_EVENTS,
+    },
+    token::Token,
 };
-use cosmwasm_std::{attr, Response};
+use cosmwasm_std::{attr, Response, Uint128};
+
+use super::{compound::take_staking_revenue, verify_funds, Context};
 
-use super::Context;
+/// Max number of concurrent stake locks per account
+pub const MAX_STAKE_LOCKS: usize = 20;
 
 pub fn exec_stake(
     ctx: Context,
     params: StakeMsg,
+) -> Result<Response, ContractError> {
+    // CW20 staking tokens can only be staked through a CW20 Send, which
+    // proves that the tokens were actually received.
+    let token = STAKING_TOKEN.load(ctx.deps.storage)?;
+    if let Token::Address(_) = token {
+        return Err(ContractError::NotAuthorized {
+            reason: "CW20 staking token must be staked via Receive".to_owned(),
+        });
+    }
+
+    // Ensure the native staking token was sent and refund any overpayment
+    let sender = ctx.info.sender.to_owned();
+    let excess = verify_funds(&ctx.info.funds, &token, params.amount)?;
+    let mut resp = process_stake(ctx, params)?;
+    if !excess.is_zero() {
+        resp = resp
+            .add_attribute("refund", excess.to_string())
+            .add_submessage(token.transfer(&sender, excess)?);
+    }
+
+    Ok(resp)
+}
+
+/// Increase the staker's delegation by an amount already received by the
+/// contract.
+pub fn process_stake(
+    ctx: Context,
+    params: StakeMsg,
 ) -> Result<Response, ContractError> {
     let Context { deps, env, info } = ctx;
     let seq_no = SEQ_NO.load(deps.storage)?;
@@ -25,74 +60,118 @@ pub fn exec_stake(
     let StakeMsg {
         amount,
         address: recipient,
+        lock_seconds,
     } = params;
 
+    // Reject dust stakes below the configured minimum increment
+    let min_increment = MIN_STAKE_INCREMENT
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    if amount < min_increment {
+        return Err(ContractError::BelowMinStakeIncrement {
+            amount,
+            min_increment,
+        });
+    }
+
     // Stake on behalf of any specified recipient or default to tx sender
     let token = STAKING_TOKEN.load(deps.storage)?;
     let staker = recipient.unwrap_or(info.sender.to_owned());
 
+    // Only the staker can lock their own stake, since locks restrict unstaking
+    if lock_seconds.is_some() && staker != info.sender {
+        return Err(ContractError::NotAuthorized {
+            reason: "only the staker can lock their stake".to_owned(),
+        });
+    }
+
+    // Staking token revenue compounded into the delegation being added
+    let mut compounded = Uint128::zero();
+
     // Get or create stake account
     let mut account = if let Some(account) = ACCOUNTS.may_load(deps.storage, &staker)? {
-        let results = sync_account(
-            deps.storage,
-            deps.api,
-            &staker,
-            &account,
-            seq_no,
-            Some(token.to_owned()),
-            false,
-        )?;
-        for (result, state) in results.iter() {
-            persist_sync_results(deps.storage, &info.sender, result, state)?;
-        }
         account
     } else {
-        // Add to amortization queue since account is new
-        AMORTIZATION_QUEUE.push_back(deps.storage, &staker)?;
+        // Add to amortization queue since account is new, unless the address
+        // is still queued from when it was last closed
+        if CLOSED_QUEUED_ACCOUNTS.has(deps.storage, &staker) {
+            CLOSED_QUEUED_ACCOUNTS.remove(deps.storage, &staker);
+        } else {
+            AMORTIZATION_QUEUE.push_back(deps.storage, &staker)?;
+        }
         N_ACCOUNTS.update(deps.storage, |n| -> Result<_, ContractError> {
             add_u32(n, 1)
         })?;
-        Account::new(t, seq_no)
+        Account::new(t, seq_no, load_delegation_scale(deps.storage)?)
     };
 
-    account.add_delegation(amount)?;
+    // Sync the account before changing its weight. New accounts are synced
+    // too, which starts their reward indexes at current values.
+    let mut results = sync_account(
+        deps.storage,
+        &staker,
+        &account,
+        seq_no,
+        None,
+        Some(MAX_IMPLICIT_SYNC_EVENTS),
+    )?;
+    pin_legacy_weight(deps.storage, &staker, &account, &results, seq_no)?;
+    if account.auto_compound {
+        compounded = take_staking_revenue(deps.storage, &mut results, &token)?;
+    }
+    for (result, state) in results.iter() {
+        persist_sync_results(deps.storage, &staker, result, state)?;
+    }
+
+    // Apply any reduction of delegation by payouts since the last rescale
+    account.rescale(load_delegation_scale(deps.storage)?)?;
+
+    let prev_weight = account.weight()?;
+
+    account.release_expired_locks(t);
+    account.add_delegation(add_u128(amount, compounded)?)?;
+
+    // Lock the new stake for the duration of the requested lock tier
+    if let Some(lock_seconds) = lock_seconds {
+        let tier = LOCK_TIERS
+            .may_load(deps.storage)?
+            .unwrap_or_default()
+            .into_iter()
+            .find(|tier| tier.seconds == lock_seconds)
+            .ok_or_else(|| ContractError::ValidationError {
+                reason: format!("no lock tier exists for {} seconds", lock_seconds),
+            })?;
+
+        if account.locks.len() >= MAX_STAKE_LOCKS {
+            return Err(ContractError::ValidationError {
+                reason: format!("cannot exceed {} stake locks", MAX_STAKE_LOCKS),
+            });
+        }
+
+        account.locks.push(StakeLock {
+            amount,
+            multiplier: tier.multiplier,
+            unlocks_at: t.plus_seconds(lock_seconds.u64()),
+        });
+    }
 
     // Save account now that it has been synced and delegation incremented
     ACCOUNTS.save(deps.storage, &staker, &account)?;
 
     // Increment total delegation across all accounts
     TOTAL_DELEGATION.update(deps.storage, |delegation| -> Result<_, ContractError> {
-        add_u128(delegation, amount)
+        add_u128(delegation, add_u128(amount, compounded)?)
     })?;
 
-    // Upsert a delegation event for this delegator
-    TS_STAKE.update(
-        deps.storage,
-        (&staker, seq_no.u64()),
-        |maybe_event| -> Result<_, ContractError> {
-            if let Some(mut event) = maybe_event {
-                event.delta = add_u128(event.delta, amount)?;
-                Ok(event)
-            } else {
-                Ok(StakingEvent {
-                    delta: account.delegation,
-                })
-            }
-        },
-    )?;
+    // Update total weight to reflect the account's new weight
+    update_account_weight(deps.storage, prev_weight, account.weight()?)?;
 
-    // Increment trigger to indicate that next deposit should create new event.
-    X.update(deps.storage, |x| -> Result<_, ContractError> {
-        add_u64(x, 1u64)
-    })?;
+    amortize(deps.storage, &env.block, seq_no, Some(staker))?;
 
-    amortize(
-        deps.storage,
-        deps.api,
-        seq_no,
-        Some(token.to_owned()),
-        Some(staker),
-    )?;
+    let mut attrs = vec![attr("action", "stake")];
+    if !compounded.is_zero() {
+        attrs.push(attr("compounded", compounded.to_string()));
+    }
 
-    Ok(Response::new().add_attributes(vec![attr("action", "stake")]))
+    Ok(Response::new().add_attributes(attrs))
 }
@@ -0,0 +1,55 @@
+use crate::{
+    error::ContractError,
+    state::storage::{ACCOUNTS, SEQ_NO},
+    sync::{persist_sync_results, sync_account},
+};
+use cosmwasm_std::{attr, Addr, Response};
+
+use super::Context;
+
+/// Default number of legacy events to sync per SyncAccount execution
+const DEFAULT_MAX_SYNC_EVENTS: u32 = 500;
+
+/// Sync up to max_events of an account's pending legacy events, advancing its
+/// sync state so that subsequent executions pick up where this one left off.
+pub fn exec_sync_account(
+    ctx: Context,
+    address: Addr,
+    max_events: Option<u32>,
+) -> Result<Response, ContractError> {
+    let Context { deps, .. } = ctx;
+    let seq_no = SEQ_NO.load(deps.storage)?;
+    let max_events = max_events.unwrap_or(DEFAULT_MAX_SYNC_EVENTS);
+
+    let account =
+        ACCOUNTS
+            .may_load(deps.storage, &address)?
+            .ok_or_else(|| ContractError::NotAuthorized {
+                reason: "Account not found".to_owned(),
+            })?;
+
+    let results = sync_account(
+        deps.storage,
+        &address,
+        &account,
+        seq_no,
+        None,
+        Some(max_events),
+    )?;
+
+    let mut n_events: u32 = 0;
+    let mut is_complete = true;
+
+    for (result, state) in results.iter() {
+        persist_sync_results(deps.storage, &address, result, state)?;
+        n_events += result.n_legacy_events;
+        is_complete &= state.seq_no >= seq_no;
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "sync_account"),
+        attr("address", address.to_string()),
+        attr("n_events", n_events.to_string()),
+        attr("complete", is_complete.to_string()),
+    ]))
+}
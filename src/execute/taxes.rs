@@ -0,0 +1,239 @@
+use crate::{
+    error::ContractError,
+    math::sub_u128,
+    msg::TaxRecipientInitArgs,
+    state::{
+        models::TaxRecipientConfig,
+        save_tax_recipient,
+        storage::{
+            TAX_RECIPIENT_CONFIGS, TAX_RECIPIENT_INFOS, TAX_RECIPIENT_TOTALS, TAX_TOTAL_BALANCES,
+        },
+        validate_tax_pct_total,
+    },
+    token::Token,
+};
+use cosmwasm_std::{attr, Addr, Attribute, Order, Response, StdResult, Storage, SubMsg, Uint128};
+
+use super::{validate_text, Context};
+
+/// Max length of tax recipient name
+const MAX_NAME_LEN: usize = 64;
+
+/// Max length of tax recipient logo URL
+const MAX_LOGO_LEN: usize = 500;
+
+/// Transfer a tax recipient's pending (non-autosend) tax balances to them
+pub fn exec_claim_taxes(
+    ctx: Context,
+    tokens: Option<Vec<Token>>,
+) -> Result<Response, ContractError> {
+    let Context { deps, info, .. } = ctx;
+
+    if !TAX_RECIPIENT_CONFIGS.has(deps.storage, &info.sender) {
+        return Err(ContractError::NotAuthorized {
+            reason: "sender is not a tax recipient".to_owned(),
+        });
+    }
+
+    let (transfer_submsgs, attrs) = settle_tax_balances(deps.storage, &info.sender, tokens)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "claim_taxes")
+        .add_attributes(attrs)
+        .add_submessages(transfer_submsgs))
+}
+
+/// Add a new tax recipient. Called by the manager.
+pub fn exec_add_tax_recipient(
+    ctx: Context,
+    args: TaxRecipientInitArgs,
+) -> Result<Response, ContractError> {
+    let Context { deps, .. } = ctx;
+
+    validate_text("name", &args.name, MAX_NAME_LEN)?;
+    validate_text("logo", &args.logo, MAX_LOGO_LEN)?;
+
+    let address = deps.api.addr_validate(args.address.as_str())?;
+
+    if TAX_RECIPIENT_CONFIGS.has(deps.storage, &address) {
+        return Err(ContractError::ValidationError {
+            reason: "tax recipient already exists".to_owned(),
+        });
+    }
+
+    save_tax_recipient(deps.storage, deps.api, &args)?;
+    let total_pct = validate_tax_pct_total(deps.storage)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "add_tax_recipient"),
+        attr("address", address.to_string()),
+        attr("pct", args.pct.to_string()),
+        attr("total_pct", total_pct.to_string()),
+    ]))
+}
+
+/// Update a mutable tax recipient's config. Called by the manager.
+pub fn exec_update_tax_recipient(
+    ctx: Context,
+    address: Addr,
+    pct: Option<Uint128>,
+    autosend: Option<bool>,
+    immutable: Option<bool>,
+) -> Result<Response, ContractError> {
+    let Context { deps, .. } = ctx;
+
+    let mut config = load_mutable_tax_recipient_config(deps.storage, &address)?;
+
+    if let Some(pct) = pct {
+        config.pct = pct;
+    }
+    if let Some(autosend) = autosend {
+        config.autosend = autosend;
+    }
+    if let Some(immutable) = immutable {
+        config.immutable = immutable;
+    }
+
+    TAX_RECIPIENT_CONFIGS.save(deps.storage, &address, &config)?;
+
+    let total_pct = validate_tax_pct_total(deps.storage)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_tax_recipient"),
+        attr("address", address.to_string()),
+        attr("pct", config.pct.to_string()),
+        attr("autosend", config.autosend.to_string()),
+        attr("immutable", config.immutable.to_string()),
+        attr("total_pct", total_pct.to_string()),
+    ]))
+}
+
+/// Remove a mutable tax recipient, first sending them any pending balances.
+/// Called by the manager.
+pub fn exec_remove_tax_recipient(
+    ctx: Context,
+    address: Addr,
+) -> Result<Response, ContractError> {
+    let Context { deps, .. } = ctx;
+
+    load_mutable_tax_recipient_config(deps.storage, &address)?;
+
+    let (transfer_submsgs, attrs) = settle_tax_balances(deps.storage, &address, None)?;
+
+    TAX_RECIPIENT_CONFIGS.remove(deps.storage, &address);
+    TAX_RECIPIENT_INFOS.remove(deps.storage, &address);
+
+    Ok(Response::new()
+        .add_attributes(vec![
+            attr("action", "remove_tax_recipient"),
+            attr("address", address.to_string()),
+        ])
+        .add_attributes(attrs)
+        .add_submessages(transfer_submsgs))
+}
+
+/// Update the sender's own tax recipient name and/or logo, keeping whichever
+/// field isn't given
+pub fn exec_update_tax_recipient_info(
+    ctx: Context,
+    name: Option<String>,
+    logo: Option<String>,
+) -> Result<Response, ContractError> {
+    let Context { deps, info, .. } = ctx;
+
+    let mut recipient_info = TAX_RECIPIENT_INFOS
+        .may_load(deps.storage, &info.sender)?
+        .ok_or_else(|| ContractError::NotAuthorized {
+            reason: "sender is not a tax recipient".to_owned(),
+        })?;
+
+    validate_text("name", &name, MAX_NAME_LEN)?;
+    validate_text("logo", &logo, MAX_LOGO_LEN)?;
+
+    if name.is_some() {
+        recipient_info.name = name.to_owned();
+    }
+    if logo.is_some() {
+        recipient_info.logo = logo.to_owned();
+    }
+
+    TAX_RECIPIENT_INFOS.save(deps.storage, &info.sender, &recipient_info)?;
+
+    // Omit unset fields, since attribute values can't be empty
+    let mut attrs = vec![
+        attr("action", "update_tax_recipient_info"),
+        attr("address", info.sender.to_string()),
+    ];
+    for (key, value) in [("name", name), ("logo", logo)] {
+        if let Some(value) = value {
+            attrs.push(attr(key, value));
+        }
+    }
+
+    Ok(Response::new().add_attributes(attrs))
+}
+
+/// Load a tax recipient's config, erroring if it's missing or immutable
+fn load_mutable_tax_recipient_config(
+    store: &dyn Storage,
+    address: &Addr,
+) -> Result<TaxRecipientConfig, ContractError> {
+    let config = TAX_RECIPIENT_CONFIGS
+        .may_load(store, address)?
+        .ok_or_else(|| ContractError::ValidationError {
+            reason: "tax recipient not found".to_owned(),
+        })?;
+
+    if config.immutable {
+        return Err(ContractError::NotAuthorized {
+            reason: "tax recipient is immutable".to_owned(),
+        });
+    }
+
+    Ok(config)
+}
+
+/// Zero out the recipient's pending tax balances for the given tokens (or all
+/// tokens if none given), returning transfer submsgs and payout attributes.
+pub fn settle_tax_balances(
+    store: &mut dyn Storage,
+    recipient: &Addr,
+    tokens: Option<Vec<Token>>,
+) -> Result<(Vec<SubMsg>, Vec<Attribute>), ContractError> {
+    let token_keys: Vec<String> = if let Some(tokens) = tokens {
+        tokens.iter().map(|t| t.to_key()).collect()
+    } else {
+        TAX_RECIPIENT_TOTALS
+            .prefix(recipient)
+            .keys(store, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?
+    };
+
+    let mut transfer_submsgs: Vec<SubMsg> = Vec::with_capacity(token_keys.len());
+    let mut attrs: Vec<Attribute> = Vec::with_capacity(2 * token_keys.len());
+
+    for token_key in token_keys.iter() {
+        if let Some(mut totals) = TAX_RECIPIENT_TOTALS.may_load(store, (recipient, token_key))? {
+            let amount = totals.balance;
+            if amount.is_zero() {
+                continue;
+            }
+
+            let token = Token::from_key(token_key);
+            transfer_submsgs.push(token.transfer(recipient, amount)?);
+
+            // Decrement total amount held for taxes with respect to this token type
+            TAX_TOTAL_BALANCES.update(store, token_key, |n| -> Result<_, ContractError> {
+                sub_u128(n.unwrap_or_default(), amount)
+            })?;
+
+            totals.balance = Uint128::zero();
+            TAX_RECIPIENT_TOTALS.save(store, (recipient, token_key), &totals)?;
+
+            attrs.push(attr("token", token_key));
+            attrs.push(attr("amount", amount.to_string()));
+        }
+    }
+
+    Ok((transfer_submsgs, attrs))
+}
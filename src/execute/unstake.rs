@@ -1,19 +1,26 @@
 use crate::{
     error::ContractError,
-    math::{add_u128, add_u64, mul_ratio_u128, sub_u128},
+    math::{add_u128, mul_ratio_u128, sub_u128},
     msg::UnstakeMsg,
     state::{
-        models::{AccountUnbondingState, StakingEvent},
+        models::AccountUnbondingState,
         storage::{
-            ACCOUNTS, ACCOUNT_UNBONDINGS, MANAGED_BY, SEQ_NO, TOTAL_DELEGATION, TOTAL_UNBONDING,
-            TS_STAKE, UNBONDING_SECONDS, X,
+            ACCOUNTS, INSTANT_UNSTAKE_CONFIG, SEQ_NO, STAKING_TOKEN, TOTAL_DELEGATION,
+            TOTAL_UNBONDING, TOTAL_WEIGHT, UNBONDING_SECONDS,
         },
     },
-    sync::{amortize, persist_sync_results, sync_account},
+    sync::{
+        amortize, ensure_legacy_synced, load_delegation_scale, load_unbondings,
+        persist_sync_results, save_unbondings, sync_account, update_account_weight,
+        MAX_IMPLICIT_SYNC_EVENTS,
+    },
 };
-use cosmwasm_std::{attr, ensure_eq, Attribute, Response};
+use cosmwasm_std::{attr, Attribute, Response, SubMsg, Uint128};
+
+use super::{close_account_if_empty, deposit::distribute, ensure_manager, Context};
 
-use super::Context;
+/// Max number of concurrent unbonding tranches per account
+pub const MAX_UNBONDING_TRANCHES: usize = 20;
 
 pub fn exec_unstake(
     ctx: Context,
@@ -23,43 +30,70 @@ pub fn exec_unstake(
     let UnstakeMsg {
         amount: maybe_amount,
         address,
+        instant,
     } = params;
 
-    // Unstake on behalf of any specified recipient or default to tx sender
+    // Unstake on behalf of any specified recipient or default to tx sender.
+    // Only the account owner can accept the instant unstake penalty.
     let account_addr = if let Some(account_addr) = address {
-        ensure_eq!(
-            account_addr,
-            MANAGED_BY.load(deps.storage)?,
-            ContractError::NotAuthorized {
-                reason: "only the contract manager can unstake on behalf of another account"
-                    .to_owned()
+        if account_addr != info.sender {
+            ensure_manager(deps.storage, &info.sender)?;
+            if instant.unwrap_or(false) {
+                return Err(ContractError::NotAuthorized {
+                    reason: "only the account owner can unstake instantly".to_owned(),
+                });
             }
-        );
+        }
         account_addr
     } else {
         info.sender.to_owned()
     };
 
     let mut attrs: Vec<Attribute> = vec![attr("action", "unstake")];
+    let mut submsgs: Vec<SubMsg> = Vec::with_capacity(2);
     let seq_no = SEQ_NO.load(deps.storage)?;
 
     // Get or create delegator's account
     if let Some(mut account) = ACCOUNTS.may_load(deps.storage, &account_addr)? {
-        let amount = maybe_amount.unwrap_or(account.delegation);
-
         // Eagerly sync account before adding new delegation
         let results = sync_account(
             deps.storage,
-            deps.api,
             &account_addr,
             &account,
             seq_no,
             None,
-            false,
+            Some(MAX_IMPLICIT_SYNC_EVENTS),
         )?;
+        ensure_legacy_synced(&results, seq_no)?;
 
         for (result, state) in results.iter() {
-            persist_sync_results(deps.storage, &info.sender, result, state)?;
+            persist_sync_results(deps.storage, &account_addr, result, state)?;
+        }
+
+        // Apply any reduction of delegation by payouts since the last rescale
+        account.rescale(load_delegation_scale(deps.storage)?)?;
+
+        let prev_weight = account.weight()?;
+
+        // Only delegation that isn't locked can be unstaked
+        account.release_expired_locks(env.block.time);
+
+        let unlocked_delegation = account.unlocked_delegation()?;
+        let amount = maybe_amount.unwrap_or(unlocked_delegation);
+
+        if amount.is_zero() {
+            return Err(ContractError::ValidationError {
+                reason: "amount must be greater than zero".to_owned(),
+            });
+        }
+
+        if amount > unlocked_delegation {
+            return Err(ContractError::ValidationError {
+                reason: format!(
+                    "amount exceeds unlocked delegation of {}",
+                    unlocked_delegation
+                ),
+            });
         }
 
         // Decrement delegation amount
@@ -72,54 +106,69 @@ pub fn exec_unstake(
             sub_u128(n, amount)
         })?;
 
-        // Increase total unbonding amount
-        TOTAL_UNBONDING.update(deps.storage, |n| -> Result<_, ContractError> {
-            add_u128(n, amount)
-        })?;
+        // Update total weight to reflect the account's new weight
+        update_account_weight(deps.storage, prev_weight, account.weight()?)?;
+
+        if instant.unwrap_or(false) {
+            let config = INSTANT_UNSTAKE_CONFIG
+                .may_load(deps.storage)?
+                .ok_or_else(|| ContractError::NotAuthorized {
+                    reason: "instant unstake is disabled".to_owned(),
+                })?;
+
+            // Redistribute the penalty to remaining stakers as staking token
+            // revenue, without burning it or counting it as a deposit. If
+            // nobody else is staked, the penalty is waived.
+            let token = STAKING_TOKEN.load(deps.storage)?;
+            let penalty = if TOTAL_WEIGHT.load(deps.storage)?.is_zero() {
+                Uint128::zero()
+            } else {
+                mul_ratio_u128(amount, config.penalty_pct, 1_000_000u128)?
+            };
+
+            if !penalty.is_zero() {
+                submsgs.extend(distribute(deps.storage, &token, penalty, config.taxed)?);
+            }
 
-        // Upsert a delegation event for this delegator
-        TS_STAKE.update(
-            deps.storage,
-            (&account_addr, seq_no.u64()),
-            |maybe_event| -> Result<_, ContractError> {
-                if let Some(mut event) = maybe_event {
-                    event.delta = sub_u128(event.delta, amount)?;
-                    Ok(event)
-                } else {
-                    Ok(StakingEvent {
-                        delta: account.delegation,
-                    })
-                }
-            },
-        )?;
+            let payout = sub_u128(amount, penalty)?;
+            if !payout.is_zero() {
+                submsgs.push(token.transfer(&account_addr, payout)?);
+            }
 
-        let duration_seconds: u64 = UNBONDING_SECONDS.load(deps.storage)?.into();
+            attrs.push(attr("instant", "true"));
+            attrs.push(attr("penalty", penalty.to_string()));
+            attrs.push(attr("payout", payout.to_string()));
+        } else {
+            // Increase total unbonding amount
+            TOTAL_UNBONDING.update(deps.storage, |n| -> Result<_, ContractError> {
+                add_u128(n, amount)
+            })?;
+
+            let duration_seconds: u64 = UNBONDING_SECONDS.load(deps.storage)?.into();
+
+            let unbonds_at = env.block.time.plus_seconds(duration_seconds);
+
+            // Add a new unbonding tranche with its own maturity. Unstakes within
+            // the same block are merged into a single tranche.
+            let mut tranches = load_unbondings(deps.storage, &account_addr)?;
+            if let Some(last) = tranches.last_mut().filter(|t| t.unbonds_at == unbonds_at) {
+                last.amount = add_u128(last.amount, amount)?;
+            } else if tranches.len() >= MAX_UNBONDING_TRANCHES {
+                return Err(ContractError::ValidationError {
+                    reason: format!(
+                        "cannot exceed {} unbonding tranches, claim matured tranches first",
+                        MAX_UNBONDING_TRANCHES
+                    ),
+                });
+            } else {
+                tranches.push(AccountUnbondingState { amount, unbonds_at });
+            }
+            save_unbondings(deps.storage, &account_addr, &tranches)?;
 
-        let unbonding = ACCOUNT_UNBONDINGS.update(
-            deps.storage,
-            &account_addr,
-            |maybe_unbonding| -> Result<_, ContractError> {
-                Ok(if let Some(mut unbonding) = maybe_unbonding {
-                    let total = add_u128(unbonding.amount, amount)?;
-                    let new_ends_at = unbonding.unbonds_at.plus_seconds(
-                        mul_ratio_u128(duration_seconds as u128, amount, total)?
-                            .u128()
-                            .clamp(0u128, u64::MAX as u128) as u64,
-                    );
-                    unbonding.amount = total;
-                    unbonding.unbonds_at = new_ends_at;
-                    unbonding
-                } else {
-                    AccountUnbondingState {
-                        amount: amount.to_owned(),
-                        unbonds_at: env.block.time.plus_seconds(duration_seconds),
-                    }
-                })
-            },
-        )?;
+            attrs.push(attr("unbonds_at", unbonds_at.nanos().to_string()));
+            attrs.push(attr("unbond_amount", amount.u128().to_string()));
+        }
 
-        attrs.push(attr("unbonds_at", unbonding.unbonds_at.nanos().to_string()));
-        attrs.push(attr("unbond_amount", unbonding.amount.u128().to_string()));
         attrs.push(attr("initiated_at", env.block.time.nanos().to_string()));
     } else {
         return Err(ContractError::NotAuthorized {
@@ -127,12 +176,18 @@ pub fn exec_unstake(
         });
     }
 
-    // Increment trigger to indicate that next deposit should create new event.
-    X.update(deps.storage, |x| -> Result<_, ContractError> {
-        add_u64(x, 1u64)
-    })?;
+    amortize(
+        deps.storage,
+        &env.block,
+        seq_no,
+        Some(account_addr.to_owned()),
+    )?;
 
-    amortize(deps.storage, deps.api, seq_no, None, Some(account_addr))?;
+    if close_account_if_empty(deps.storage, &account_addr)? {
+        attrs.push(attr("closed", "true"));
+    }
 
-    Ok(Response::new().add_attributes(attrs))
+    Ok(Response::new()
+        .add_attributes(attrs)
+        .add_submessages(submsgs))
 }
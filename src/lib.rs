@@ -9,5 +9,7 @@ pub mod msg;
 pub mod query;
 pub mod responses;
 pub mod state;
+#[cfg(not(feature = "library"))]
+pub mod sudo;
 pub mod sync;
 pub mod token;
@@ -1,4 +1,4 @@
-use cosmwasm_std::{OverflowError, OverflowOperation, StdError, Uint128, Uint256, Uint64};
+use cosmwasm_std::{OverflowError, OverflowOperation, StdError, Uint128, Uint256, Uint512, Uint64};
 
 use crate::error::ContractError;
 
@@ -42,6 +42,34 @@ pub fn div_u256<A: Into<Uint256>, B: Into<Uint256>>(
         .map_err(|e| ContractError::Std(StdError::divide_by_zero(e)))
 }
 
+pub fn u256_to_u128(value: Uint256) -> Result<Uint128, ContractError> {
+    Uint128::try_from(value).map_err(|e| ContractError::Std(StdError::from(e)))
+}
+
+pub fn mul_u512<A: Into<Uint512>, B: Into<Uint512>>(
+    a: A,
+    b: B,
+) -> Result<Uint512, ContractError> {
+    let a: Uint512 = a.into();
+    let b: Uint512 = b.into();
+    a.checked_mul(b)
+        .map_err(|e| ContractError::Std(StdError::overflow(e)))
+}
+
+pub fn div_u512<A: Into<Uint512>, B: Into<Uint512>>(
+    numerator: A,
+    denominator: B,
+) -> Result<Uint512, ContractError> {
+    let a: Uint512 = numerator.into();
+    let b: Uint512 = denominator.into();
+    a.checked_div(b)
+        .map_err(|e| ContractError::Std(StdError::divide_by_zero(e)))
+}
+
+pub fn u512_to_u256(value: Uint512) -> Result<Uint256, ContractError> {
+    Uint256::try_from(value).map_err(|e| ContractError::Std(StdError::from(e)))
+}
+
 pub fn add_u128<A: Into<Uint128>, B: Into<Uint128>>(
     a: A,
     b: B,
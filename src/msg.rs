@@ -1,5 +1,6 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128, Uint64};
+use cosmwasm_std::{Addr, Binary, Timestamp, Uint128, Uint64};
+use cw20::Cw20ReceiveMsg;
 
 use crate::token::Token;
 
@@ -8,6 +9,7 @@ pub struct InstantiateMsg {
     pub marketing: HouseMarketingInfo,
     pub taxes: Vec<TaxRecipientInitArgs>,
     pub staking: StakingConfig,
+    pub burn_pct: Option<Uint128>,
 }
 
 #[cw_serde]
@@ -16,6 +18,15 @@ pub struct StakingConfig {
     pub revenue_tokens: Vec<Token>,
     pub min_increment: Option<Uint128>,
     pub unbonding_seconds: Option<Uint64>,
+    pub lock_tiers: Option<Vec<LockTier>>,
+}
+
+/// Duration for which stake can be locked in exchange for boosted revenue
+#[cw_serde]
+pub struct LockTier {
+    pub seconds: Uint64,
+    /// Revenue weight multiplier out of 1000000, e.g. 1500000 is 1.5x
+    pub multiplier: Uint128,
 }
 
 #[cw_serde]
@@ -39,12 +50,75 @@ pub struct HouseMarketingInfo {
 pub struct StakeMsg {
     pub amount: Uint128,
     pub address: Option<Addr>,
+    /// Lock the stake for the duration of a configured lock tier. Only allowed
+    /// when staking for oneself.
+    pub lock_seconds: Option<Uint64>,
 }
 
 #[cw_serde]
 pub struct UnstakeMsg {
     pub amount: Option<Uint128>,
     pub address: Option<Addr>,
+    /// Skip unbonding in exchange for the instant unstake penalty
+    pub instant: Option<bool>,
+}
+
+/// Penalty charged on instant unstakes and redistributed to remaining stakers
+#[cw_serde]
+pub struct InstantUnstakeConfig {
+    /// Penalty rate out of 1000000
+    pub penalty_pct: Uint128,
+    /// Whether tax recipients receive their share of the penalty
+    pub taxed: bool,
+}
+
+/// Controls how many queued accounts are synced by amortization, which runs
+/// implicitly as part of most executions
+#[cw_serde]
+pub struct AmortizationConfig {
+    /// Max number of accounts synced per execution
+    pub batch_size: u32,
+    /// Share of all accounts synced per execution out of 1000000, rounded up
+    /// and capped by batch_size
+    pub account_pct: Uint128,
+}
+
+/// Limits on payouts requested by authorized games, which are drawn from
+/// staked delegation
+#[cw_serde]
+pub struct PayoutConfig {
+    /// Max payout per request out of 1000000 of total delegation
+    pub max_payout_pct: Uint128,
+    /// Duration of the window over which payouts are limited in aggregate
+    pub window_seconds: Uint64,
+    /// Max total payout per window out of 1000000 of total delegation
+    pub max_window_pct: Uint128,
+}
+
+#[cw_serde]
+pub enum DepositorCategory {
+    Game,
+    Partner,
+    Treasury,
+}
+
+impl DepositorCategory {
+    pub const ALL: [Self; 3] = [Self::Game, Self::Partner, Self::Treasury];
+
+    pub fn to_key(&self) -> &'static str {
+        match self {
+            Self::Game => "game",
+            Self::Partner => "partner",
+            Self::Treasury => "treasury",
+        }
+    }
+}
+
+/// Allowlist entry of a depositor
+#[cw_serde]
+pub struct DepositorInfo {
+    pub label: String,
+    pub category: DepositorCategory,
 }
 
 #[cw_serde]
@@ -55,23 +129,176 @@ pub struct DepositMsg {
 
 #[cw_serde]
 pub struct ClaimMsg {
-    pub token: Token,
+    /// Token to claim, or every token with a pending balance if omitted
+    pub token: Option<Token>,
+    /// Address to receive the claimed tokens, defaulting to the tx sender
+    pub recipient: Option<Addr>,
+    /// Message for the recipient contract to execute upon receipt of the
+    /// claimed token, which must be given. Matured unbonding is transferred
+    /// without it.
+    pub msg: Option<Binary>,
+}
+
+/// Hook message embedded in a CW20 Send, handled by ExecuteMsg::Receive. The
+/// amount and sender are taken from the enclosing Cw20ReceiveMsg.
+#[cw_serde]
+pub enum ReceiveHookMsg {
+    Stake {
+        address: Option<Addr>,
+        lock_seconds: Option<Uint64>,
+    },
+    Deposit {},
+}
+
+/// House configuration messages, restricted to the manager
+#[cw_serde]
+pub enum AdminMsg {
+    UpdateMarketingInfo(HouseMarketingInfo),
+    UpdateUnbondingSeconds {
+        seconds: Uint64,
+    },
+    SetMinIncrement {
+        amount: Uint128,
+    },
+    AddRevenueToken {
+        token: Token,
+    },
+    RemoveRevenueToken {
+        token: Token,
+    },
+    AddTaxRecipient(TaxRecipientInitArgs),
+    UpdateTaxRecipient {
+        address: Addr,
+        pct: Option<Uint128>,
+        autosend: Option<bool>,
+        immutable: Option<bool>,
+    },
+    RemoveTaxRecipient {
+        address: Addr,
+    },
+    SetInstantUnstakeConfig {
+        config: Option<InstantUnstakeConfig>,
+    },
+    SetAmortizationConfig {
+        config: AmortizationConfig,
+    },
+    AddGame {
+        address: Addr,
+    },
+    RemoveGame {
+        address: Addr,
+    },
+    SetPayoutConfig {
+        config: Option<PayoutConfig>,
+    },
+    /// Add or update a depositor allowlist entry
+    SetDepositor {
+        address: Addr,
+        info: DepositorInfo,
+    },
+    RemoveDepositor {
+        address: Addr,
+    },
+    /// Restrict deposits to allowlisted depositors
+    SetDepositorAllowlist {
+        enabled: bool,
+    },
+    /// Set the recipient of slashed stake, or burn it if None, which requires
+    /// the contract to be able to burn the staking token
+    SetSlashRecipient {
+        address: Option<Addr>,
+    },
+    /// Slash all delegation and unbonding by a rate out of 1000000
+    Slash {
+        ratio: Uint128,
+    },
+    /// Slash an account's delegation by an amount and its unbonding by the
+    /// same proportion
+    SlashAccount {
+        address: Addr,
+        amount: Uint128,
+    },
 }
 
 #[cw_serde]
 pub enum ExecuteMsg {
+    Admin(AdminMsg),
+    Receive(Cw20ReceiveMsg),
     Deposit(DepositMsg),
     Stake(StakeMsg),
     Unstake(UnstakeMsg),
+    CancelUnbonding {
+        amount: Option<Uint128>,
+    },
     Claim(ClaimMsg),
+    Compound {},
+    SetAutoCompound {
+        enabled: bool,
+    },
+    ClaimTaxes {
+        tokens: Option<Vec<Token>>,
+    },
+    UpdateTaxRecipientInfo {
+        name: Option<String>,
+        logo: Option<String>,
+    },
+    ProposeManager {
+        address: Addr,
+        expires_at: Option<Timestamp>,
+    },
+    AcceptManager {},
+    CancelManagerProposal {},
+    /// Sync up to max_events of an account's legacy events, which claiming
+    /// and unstaking require once more are pending than they sync themselves
+    SyncAccount {
+        address: Addr,
+        max_events: Option<u32>,
+    },
+    Crank {
+        limit: Option<u32>,
+    },
+    /// Pay a game's winner from staked delegation, restricted to games
+    RequestPayout {
+        recipient: Addr,
+        token: Token,
+        amount: Uint128,
+    },
 }
 
 #[cw_serde]
 pub enum QueryMsg {
-    Account { address: Addr },
+    Account {
+        address: Addr,
+    },
+    Accounts {
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
+    /// Accounts with the most delegation. Accounts saved by versions prior to
+    /// the delegation index are omitted until next synced by the crank.
+    TopStakers {
+        limit: Option<u32>,
+    },
     House {},
-    Deposits {},
+    Config {},
+    Deposits {
+        token: Option<Token>,
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
+    Depositor {
+        address: Addr,
+    },
     Taxes {},
+    PendingManager {},
+    Crank {},
+}
+
+/// Messages sent by chain modules through the sudo entrypoint
+#[cw_serde]
+pub enum SudoMsg {
+    /// Sent by Juno's clock module at the end of every block
+    ClockEndBlock {},
 }
 
 #[cw_serde]
@@ -1,14 +1,14 @@
-use cosmwasm_std::{Addr, Order, StdResult, Uint128};
+use cosmwasm_std::Addr;
 
 use crate::{
     error::ContractError,
     responses::AccountResponse,
-    state::{
-        models::AccountSyncState,
-        storage::{ACCOUNTS, ACCOUNT_SYNC_INFOS, ACCOUNT_UNBONDINGS, BALANCES, SEQ_NO},
+    state::storage::{ACCOUNTS, SEQ_NO},
+    sync::{
+        load_delegation_scale, load_reward_tokens, load_sync_state, load_unbondings,
+        sync_account_balance,
     },
-    sync::sync_account_balance,
-    token::{Token, TokenAmount},
+    token::TokenAmount,
 };
 
 use super::ReadonlyContext;
@@ -20,34 +20,20 @@ pub fn query_account(
     let ReadonlyContext { deps, .. } = ctx;
     let seq_no = SEQ_NO.load(deps.storage)?;
 
-    if let Some(account) = ACCOUNTS.may_load(deps.storage, &address)? {
+    if let Some(mut account) = ACCOUNTS.may_load(deps.storage, &address)? {
         let mut balances: Vec<TokenAmount> = Vec::with_capacity(2);
 
-        for result in BALANCES
-            .keys(deps.storage, None, None, Order::Ascending)
-            .collect::<Vec<StdResult<_>>>()
-        {
-            let token_key = result?;
-            let token = Token::from_key(&token_key);
-            let mut sync_state = ACCOUNT_SYNC_INFOS
-                .may_load(deps.storage, (&address, &token.to_key()))?
-                .or_else(|| {
-                    Some(AccountSyncState {
-                        amount: Uint128::zero(),
-                        seq_no: account.created_at_seq_no,
-                        t: account.created_at,
-                    })
-                })
-                .unwrap();
+        for token in load_reward_tokens(deps.storage)? {
+            let mut sync_state = load_sync_state(deps.storage, &address, &account, &token)?;
 
             sync_account_balance(
                 deps.storage,
-                deps.api,
                 &address,
+                &account,
                 &mut sync_state,
                 &token,
                 seq_no,
-                true,
+                None,
             )?;
 
             balances.push(TokenAmount {
@@ -56,10 +42,16 @@ pub fn query_account(
             })
         }
 
+        // Show delegation net of payouts not yet applied to the account
+        account.rescale(load_delegation_scale(deps.storage)?)?;
+
         return Ok(Some(AccountResponse {
             created_at: account.created_at,
             delegation: account.delegation,
-            unbonding: ACCOUNT_UNBONDINGS.may_load(deps.storage, &address)?,
+            weight: account.weight()?,
+            locks: account.locks,
+            auto_compound: account.auto_compound,
+            unbondings: load_unbondings(deps.storage, &address)?,
             balances,
         }));
     }
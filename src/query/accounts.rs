@@ -0,0 +1,87 @@
+use cosmwasm_std::{Addr, Order};
+use cw_storage_plus::Bound;
+
+use crate::{
+    error::ContractError,
+    responses::StakerResponseItem,
+    state::{
+        models::{Account, Scale},
+        storage::ACCOUNTS,
+    },
+    sync::load_delegation_scale,
+};
+
+use super::ReadonlyContext;
+
+/// Number of accounts returned per page unless otherwise specified
+const DEFAULT_ACCOUNTS_LIMIT: u32 = 20;
+
+/// Max number of accounts returned per page
+const MAX_ACCOUNTS_LIMIT: u32 = 100;
+
+/// Page through accounts in address order
+pub fn query_accounts(
+    ctx: ReadonlyContext,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> Result<Vec<StakerResponseItem>, ContractError> {
+    let ReadonlyContext { deps, .. } = ctx;
+    let limit = limit
+        .unwrap_or(DEFAULT_ACCOUNTS_LIMIT)
+        .min(MAX_ACCOUNTS_LIMIT) as usize;
+    let scale = load_delegation_scale(deps.storage)?;
+
+    ACCOUNTS
+        .range(
+            deps.storage,
+            start_after.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|r| -> Result<_, ContractError> {
+            let (address, account) = r?;
+            to_staker_response_item(address, account, &scale)
+        })
+        .collect::<Result<Vec<_>, _>>()
+}
+
+/// Accounts with the most delegation, in descending order
+pub fn query_top_stakers(
+    ctx: ReadonlyContext,
+    limit: Option<u32>,
+) -> Result<Vec<StakerResponseItem>, ContractError> {
+    let ReadonlyContext { deps, .. } = ctx;
+    let limit = limit
+        .unwrap_or(DEFAULT_ACCOUNTS_LIMIT)
+        .min(MAX_ACCOUNTS_LIMIT) as usize;
+    let scale = load_delegation_scale(deps.storage)?;
+
+    ACCOUNTS
+        .idx
+        .delegation
+        .range(deps.storage, None, None, Order::Descending)
+        .take(limit)
+        .map(|r| -> Result<_, ContractError> {
+            let (address, account) = r?;
+            to_staker_response_item(address, account, &scale)
+        })
+        .collect::<Result<Vec<_>, _>>()
+}
+
+fn to_staker_response_item(
+    address: Addr,
+    account: Account,
+    scale: &Scale,
+) -> Result<StakerResponseItem, ContractError> {
+    let mut account = account;
+
+    // Show delegation net of payouts not yet applied to the account
+    account.rescale(scale.to_owned())?;
+
+    Ok(StakerResponseItem {
+        address,
+        delegation: account.delegation,
+        weight: account.weight()?,
+    })
+}
@@ -0,0 +1,44 @@
+use cosmwasm_std::{Order, StdResult};
+
+use crate::{
+    error::ContractError,
+    responses::ConfigResponse,
+    state::storage::{
+        AMORTIZATION_CONFIG, BURN_PCT, DEPOSITOR_ALLOWLIST_ENABLED, GAMES, LOCK_TIERS, MANAGED_BY,
+        MIN_STAKE_INCREMENT, PAYOUT_CONFIG, REVENUE_TOKEN_KEYS, SLASH_RECIPIENT, STAKING_TOKEN,
+        UNBONDING_SECONDS,
+    },
+    sync::DEFAULT_AMORTIZATION_CONFIG,
+    token::Token,
+};
+
+use super::ReadonlyContext;
+
+pub fn query_config(ctx: ReadonlyContext) -> Result<ConfigResponse, ContractError> {
+    let ReadonlyContext { deps, .. } = ctx;
+    Ok(ConfigResponse {
+        managed_by: MANAGED_BY.load(deps.storage)?,
+        staking_token: STAKING_TOKEN.load(deps.storage)?,
+        revenue_tokens: REVENUE_TOKEN_KEYS
+            .keys(deps.storage, None, None, Order::Ascending)
+            .map(|r| r.map(|k| Token::from_key(&k)))
+            .collect::<StdResult<Vec<_>>>()?,
+        min_increment: MIN_STAKE_INCREMENT
+            .may_load(deps.storage)?
+            .unwrap_or_default(),
+        unbonding_seconds: UNBONDING_SECONDS.load(deps.storage)?,
+        lock_tiers: LOCK_TIERS.may_load(deps.storage)?.unwrap_or_default(),
+        burn_pct: BURN_PCT.may_load(deps.storage)?.unwrap_or_default(),
+        amortization: AMORTIZATION_CONFIG
+            .may_load(deps.storage)?
+            .unwrap_or(DEFAULT_AMORTIZATION_CONFIG),
+        payouts: PAYOUT_CONFIG.may_load(deps.storage)?,
+        games: GAMES
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?,
+        slash_recipient: SLASH_RECIPIENT.may_load(deps.storage)?,
+        depositor_allowlist: DEPOSITOR_ALLOWLIST_ENABLED
+            .may_load(deps.storage)?
+            .unwrap_or_default(),
+    })
+}
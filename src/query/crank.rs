@@ -0,0 +1,37 @@
+use cosmwasm_std::{Order, StdResult};
+
+use crate::{
+    error::ContractError,
+    responses::CrankResponse,
+    state::storage::{
+        AMORTIZATION_CONFIG, AMORTIZATION_QUEUE, LAST_CRANK, N_ACCOUNTS, N_BALANCE_EVENTS, SEQ_NO,
+    },
+    sync::DEFAULT_AMORTIZATION_CONFIG,
+};
+
+use super::ReadonlyContext;
+
+pub fn query_crank(ctx: ReadonlyContext) -> Result<CrankResponse, ContractError> {
+    let ReadonlyContext { deps, .. } = ctx;
+
+    // Legacy events are garbage collected once every account has synced them
+    let mut n_pending_events: u32 = 0;
+    for n in N_BALANCE_EVENTS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|r| r.map(|(_, n)| n))
+        .collect::<StdResult<Vec<_>>>()?
+    {
+        n_pending_events = n_pending_events.saturating_add(n);
+    }
+
+    Ok(CrankResponse {
+        queue_size: AMORTIZATION_QUEUE.len(deps.storage)?,
+        n_accounts: N_ACCOUNTS.load(deps.storage)?,
+        n_pending_events,
+        seq_no: SEQ_NO.load(deps.storage)?,
+        last_crank: LAST_CRANK.may_load(deps.storage)?,
+        config: AMORTIZATION_CONFIG
+            .may_load(deps.storage)?
+            .unwrap_or(DEFAULT_AMORTIZATION_CONFIG),
+    })
+}
@@ -1,57 +1,154 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
-use cosmwasm_std::{Addr, Order};
+use cosmwasm_std::{Addr, Order, StdResult, Storage};
+use cw_storage_plus::Bound;
 
 use crate::{
     error::ContractError,
+    msg::DepositorCategory,
     responses::DepositsResponse,
     state::{
-        models::{DepositTokenAmount, Depositor},
-        storage::{DEPOSITOR_TOTALS, DEPOSIT_AGG_TOTALS},
+        models::{DepositCategoryTotals, DepositTokenAmount, Depositor},
+        storage::{DEPOSITORS, DEPOSITOR_TOTALS, DEPOSIT_AGG_TOTALS, DEPOSIT_CATEGORY_TOTALS},
     },
     token::Token,
 };
 
 use super::ReadonlyContext;
 
-pub fn query_deposits(ctx: ReadonlyContext) -> Result<DepositsResponse, ContractError> {
+/// Number of depositors returned per page unless otherwise specified
+const DEFAULT_DEPOSITORS_LIMIT: u32 = 20;
+
+/// Max number of depositors returned per page
+const MAX_DEPOSITORS_LIMIT: u32 = 100;
+
+pub fn query_deposits(
+    ctx: ReadonlyContext,
+    token: Option<Token>,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> Result<DepositsResponse, ContractError> {
     let ReadonlyContext { deps, .. } = ctx;
+    let limit = limit
+        .unwrap_or(DEFAULT_DEPOSITORS_LIMIT)
+        .min(MAX_DEPOSITORS_LIMIT) as usize;
 
-    // Build a hash map from depositor addr to a vec of their total deposits for
-    // each token type they've deposited.
-    let mut addr2amounts: HashMap<Addr, Vec<DepositTokenAmount>> = HashMap::with_capacity(16);
-    for result in DEPOSITOR_TOTALS.range(deps.storage, None, None, Order::Ascending) {
-        let ((token_key, addr), totals) = result?;
-        let token = Token::from_key(&token_key);
-        if let Some(amounts_vec) = addr2amounts.get_mut(&addr) {
-            amounts_vec.push(DepositTokenAmount {
-                amount: totals.amount,
-                n: totals.n,
-                token,
-            });
+    // Depositors are paginated in address order. Taking the first page of
+    // depositors of each token suffices to find the first page across all of
+    // them, along with each one's complete totals.
+    let token_keys: Vec<String> = if let Some(token) = token {
+        vec![token.to_key()]
+    } else {
+        load_deposit_token_keys(deps.storage)?
+    };
+
+    let mut addr2amounts: BTreeMap<Addr, Vec<DepositTokenAmount>> = BTreeMap::new();
+    for token_key in token_keys.iter() {
+        for result in DEPOSITOR_TOTALS
+            .prefix(token_key)
+            .range(
+                deps.storage,
+                start_after.as_ref().map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+        {
+            let (addr, totals) = result?;
+            addr2amounts
+                .entry(addr)
+                .or_default()
+                .push(DepositTokenAmount {
+                    amount: totals.amount,
+                    n: totals.n,
+                    token: Token::from_key(token_key),
+                });
+        }
+    }
+
+    // Break down totals by category of allowlisted depositor
+    let mut categories: Vec<DepositCategoryTotals> =
+        Vec::with_capacity(DepositorCategory::ALL.len());
+    for category in DepositorCategory::ALL {
+        let totals = DEPOSIT_CATEGORY_TOTALS
+            .prefix(category.to_key())
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|r| {
+                r.map(|(k, v)| DepositTokenAmount {
+                    token: Token::from_key(&k),
+                    amount: v.amount,
+                    n: v.n,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        if !totals.is_empty() {
+            categories.push(DepositCategoryTotals { category, totals });
         }
     }
 
-    return Ok(DepositsResponse {
+    Ok(DepositsResponse {
         // Aggregate grant total deposit amounts across all depositors
         totals: DEPOSIT_AGG_TOTALS
             .range(deps.storage, None, None, Order::Ascending)
             .map(|r| {
-                let (k, v) = r.unwrap();
-                DepositTokenAmount {
+                r.map(|(k, v)| DepositTokenAmount {
                     token: Token::from_key(&k),
                     amount: v.amount,
                     n: v.n,
-                }
+                })
             })
-            .collect(),
+            .collect::<StdResult<Vec<_>>>()?,
+        categories,
         // Aggregate total deposit per depositor address
         depositors: addr2amounts
-            .iter()
-            .map(|(address, totals)| Depositor {
-                address: address.to_owned(),
-                totals: totals.to_owned(),
+            .into_iter()
+            .take(limit)
+            .map(|(address, totals)| -> StdResult<_> {
+                Ok(Depositor {
+                    info: DEPOSITORS.may_load(deps.storage, &address)?,
+                    address,
+                    totals,
+                })
             })
-            .collect(),
-    });
+            .collect::<StdResult<Vec<_>>>()?,
+    })
+}
+
+/// Totals deposited by the given address for each token type, or None if it
+/// has never deposited and isn't allowlisted.
+pub fn query_depositor(
+    ctx: ReadonlyContext,
+    address: Addr,
+) -> Result<Option<Depositor>, ContractError> {
+    let ReadonlyContext { deps, .. } = ctx;
+
+    let mut totals: Vec<DepositTokenAmount> = Vec::with_capacity(2);
+    for token_key in load_deposit_token_keys(deps.storage)? {
+        if let Some(t) = DEPOSITOR_TOTALS.may_load(deps.storage, (&token_key, &address))? {
+            totals.push(DepositTokenAmount {
+                amount: t.amount,
+                n: t.n,
+                token: Token::from_key(&token_key),
+            });
+        }
+    }
+
+    let info = DEPOSITORS.may_load(deps.storage, &address)?;
+
+    if totals.is_empty() && info.is_none() {
+        return Ok(None);
+    }
+
+    Ok(Some(Depositor {
+        address,
+        totals,
+        info,
+    }))
+}
+
+/// Keys of every token type ever deposited
+fn load_deposit_token_keys(store: &dyn Storage) -> Result<Vec<String>, ContractError> {
+    Ok(DEPOSIT_AGG_TOTALS
+        .keys(store, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?)
 }
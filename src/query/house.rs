@@ -4,7 +4,7 @@ use crate::{
     error::ContractError,
     responses::{HouseResponse, HouseStats},
     state::storage::{
-        BALANCES, CREATED_AT, CREATED_BY, MARKETING_INFO, STAKING_TOKEN, N_ACCOUNTS,
+        BALANCES, CREATED_AT, CREATED_BY, MARKETING_INFO, N_ACCOUNTS, STAKING_TOKEN,
         TOTAL_DELEGATION,
     },
     token::TokenAmount,
@@ -14,7 +14,7 @@ use super::ReadonlyContext;
 
 pub fn query_house(ctx: ReadonlyContext) -> Result<HouseResponse, ContractError> {
     let ReadonlyContext { deps, .. } = ctx;
-    return Ok(HouseResponse {
+    Ok(HouseResponse {
         created_at: CREATED_AT.load(deps.storage)?,
         created_by: CREATED_BY.load(deps.storage)?,
         marketing: MARKETING_INFO.load(deps.storage)?,
@@ -29,5 +29,5 @@ pub fn query_house(ctx: ReadonlyContext) -> Result<HouseResponse, ContractError>
         stats: HouseStats {
             n_accounts: N_ACCOUNTS.load(deps.storage)?,
         },
-    });
+    })
 }
@@ -0,0 +1,13 @@
+use crate::{
+    error::ContractError,
+    state::{models::PendingManager, storage::PENDING_MANAGER},
+};
+
+use super::ReadonlyContext;
+
+pub fn query_pending_manager(
+    ctx: ReadonlyContext
+) -> Result<Option<PendingManager>, ContractError> {
+    let ReadonlyContext { deps, .. } = ctx;
+    Ok(PENDING_MANAGER.may_load(deps.storage)?)
+}
@@ -1,6 +1,10 @@
 pub mod account;
+pub mod accounts;
+pub mod config;
+pub mod crank;
 pub mod deposits;
 pub mod house;
+pub mod manager;
 pub mod taxes;
 
 use cosmwasm_std::{Deps, Env};
@@ -54,8 +54,8 @@ pub fn query_taxes(ctx: ReadonlyContext) -> Result<TaxesResponse, ContractError>
         }
     }
 
-    return Ok(TaxesResponse {
+    Ok(TaxesResponse {
         recipients: addr2recipients.values().map(|x| x.to_owned()).collect(),
         pct: agg_pct,
-    });
+    })
 }
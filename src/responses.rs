@@ -1,11 +1,11 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Timestamp, Uint128};
+use cosmwasm_std::{Addr, Timestamp, Uint128, Uint64};
 
 use crate::{
-    msg::HouseMarketingInfo,
+    msg::{AmortizationConfig, HouseMarketingInfo, LockTier, PayoutConfig},
     state::models::{
-        AccountUnbondingState, DepositTokenAmount, Depositor, TaxRecipientBalance,
-        TaxRecipientConfig, TaxRecipientInfo,
+        AccountUnbondingState, DepositCategoryTotals, DepositTokenAmount, Depositor, LastCrank,
+        StakeLock, TaxRecipientBalance, TaxRecipientConfig, TaxRecipientInfo,
     },
     token::{Token, TokenAmount},
 };
@@ -14,8 +14,18 @@ use crate::{
 pub struct AccountResponse {
     pub created_at: Timestamp,
     pub delegation: Uint128,
+    pub weight: Uint128,
+    pub locks: Vec<StakeLock>,
+    pub auto_compound: bool,
     pub balances: Vec<TokenAmount>,
-    pub unbonding: Option<AccountUnbondingState>,
+    pub unbondings: Vec<AccountUnbondingState>,
+}
+
+#[cw_serde]
+pub struct StakerResponseItem {
+    pub address: Addr,
+    pub delegation: Uint128,
+    pub weight: Uint128,
 }
 
 #[cw_serde]
@@ -28,9 +38,39 @@ pub struct HouseResponse {
     pub stats: HouseStats,
 }
 
+#[cw_serde]
+pub struct ConfigResponse {
+    pub managed_by: Addr,
+    pub staking_token: Token,
+    pub revenue_tokens: Vec<Token>,
+    pub min_increment: Uint128,
+    pub unbonding_seconds: Uint64,
+    pub lock_tiers: Vec<LockTier>,
+    pub burn_pct: Uint128,
+    pub amortization: AmortizationConfig,
+    pub payouts: Option<PayoutConfig>,
+    pub games: Vec<Addr>,
+    pub slash_recipient: Option<Addr>,
+    pub depositor_allowlist: bool,
+}
+
+#[cw_serde]
+pub struct CrankResponse {
+    pub queue_size: u32,
+    pub n_accounts: u32,
+    /// Number of legacy balance events not yet synced by every account
+    pub n_pending_events: u32,
+    pub seq_no: Uint64,
+    /// Block and size of the last crank that synced any accounts
+    pub last_crank: Option<LastCrank>,
+    pub config: AmortizationConfig,
+}
+
 #[cw_serde]
 pub struct DepositsResponse {
     pub totals: Vec<DepositTokenAmount>,
+    /// Totals deposited by allowlisted depositors of each category
+    pub categories: Vec<DepositCategoryTotals>,
     pub depositors: Vec<Depositor>,
 }
 
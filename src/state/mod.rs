@@ -1,17 +1,26 @@
 pub mod models;
 pub mod storage;
 
-use cosmwasm_std::{Response, Uint128, Uint64};
-use models::{TaxRecipientConfig, TaxRecipientInfo};
+use cosmwasm_std::{Addr, Api, Order, Response, StdResult, Storage, Uint128, Uint256, Uint64};
+use cw_storage_plus::{Item, Map};
+use models::{AccountUnbondingState, TaxRecipientConfig, TaxRecipientInfo};
 use storage::{
-    CREATED_AT, CREATED_BY, MANAGED_BY, MARKETING_INFO, N_ACCOUNTS, REVENUE_TOKEN_KEYS,
-    STAKING_TOKEN, TAX_RECIPIENT_CONFIGS, TAX_RECIPIENT_INFOS, TOTAL_UNBONDING, UNBONDING_SECONDS,
-    X,
+    CREATED_AT, CREATED_BY, MANAGED_BY, MARKETING_INFO, MIN_STAKE_INCREMENT, N_ACCOUNTS,
+    REVENUE_TOKEN_KEYS, STAKING_TOKEN, TAX_RECIPIENT_CONFIGS, TAX_RECIPIENT_INFOS, TOTAL_UNBONDING,
+    UNBONDING_SECONDS,
 };
 
-use crate::{error::ContractError, execute::Context, math::add_u128, msg::InstantiateMsg};
+use crate::{
+    error::ContractError,
+    execute::{ensure_burnable, Context},
+    math::add_u128,
+    msg::{InstantiateMsg, TaxRecipientInitArgs},
+};
 
-use self::storage::{SEQ_NO, TOTAL_DELEGATION};
+use self::storage::{
+    ACCOUNT_UNBONDINGS, BALANCES, BURN_PCT, LOCK_TIERS, N_BALANCE_EVENTS, REWARD_INDEXES, SEQ_NO,
+    TOTAL_DELEGATION, TOTAL_WEIGHT,
+};
 
 /// Top-level initialization of contract state
 pub fn init(
@@ -20,9 +29,9 @@ pub fn init(
 ) -> Result<Response, ContractError> {
     let Context { deps, info, env } = ctx;
     TOTAL_DELEGATION.save(deps.storage, &Uint128::zero())?;
+    TOTAL_WEIGHT.save(deps.storage, &Uint128::zero())?;
     TOTAL_UNBONDING.save(deps.storage, &Uint128::zero())?;
     SEQ_NO.save(deps.storage, &Uint64::zero())?;
-    X.save(deps.storage, &Uint64::zero())?;
     CREATED_AT.save(deps.storage, &env.block.time)?;
     CREATED_BY.save(deps.storage, &info.sender)?;
     MANAGED_BY.save(deps.storage, &info.sender)?;
@@ -33,41 +42,155 @@ pub fn init(
         deps.storage,
         &msg.staking.unbonding_seconds.unwrap_or_default(),
     )?;
+    MIN_STAKE_INCREMENT.save(deps.storage, &msg.staking.min_increment.unwrap_or_default())?;
+
+    // Init share of deposits to burn
+    let burn_pct = msg.burn_pct.unwrap_or_default();
+    if burn_pct > Uint128::from(1_000_000u128) {
+        return Err(ContractError::ValidationError {
+            reason: "burn rate cannot exceed 1000000 or 100%".to_owned(),
+        });
+    }
+    BURN_PCT.save(deps.storage, &burn_pct)?;
+
+    // Init lock tiers
+    let lock_tiers = msg.staking.lock_tiers.unwrap_or_default();
+    for (i, tier) in lock_tiers.iter().enumerate() {
+        if tier.seconds.is_zero() || tier.multiplier < Uint128::from(1_000_000u128) {
+            return Err(ContractError::ValidationError {
+                reason: "lock tiers must have nonzero seconds and a multiplier of at least 1000000"
+                    .to_owned(),
+            });
+        }
+        if lock_tiers[..i].iter().any(|t| t.seconds == tier.seconds) {
+            return Err(ContractError::ValidationError {
+                reason: format!("duplicate lock tier for {} seconds", tier.seconds),
+            });
+        }
+    }
+    LOCK_TIERS.save(deps.storage, &lock_tiers)?;
 
     for token in msg.staking.revenue_tokens.iter() {
+        if !burn_pct.is_zero() {
+            ensure_burnable(token, &env.contract.address)?;
+        }
         REVENUE_TOKEN_KEYS.save(deps.storage, &token.to_key(), &0)?;
     }
 
     // Init taxes
-    let mut total_tax_pct = Uint128::zero();
-    for info in msg.taxes.iter() {
-        let key = deps.api.addr_validate(info.address.as_str())?;
+    for args in msg.taxes.iter() {
+        save_tax_recipient(deps.storage, deps.api, args)?;
+    }
+    validate_tax_pct_total(deps.storage)?;
 
-        total_tax_pct = add_u128(total_tax_pct, info.pct)?.min(1_000_000u128.into());
-        if total_tax_pct > Uint128::from(1_000_000u128) {
-            return Err(ContractError::ValidationError {
-                reason: "aggregate tax rate cannot exceed 1000000 or 100%".to_owned(),
-            });
+    Ok(Response::new().add_attribute("action", "instantiate"))
+}
+
+/// Top-level migration of contract state from prior versions
+pub fn migrate(store: &mut dyn Storage) -> Result<(), ContractError> {
+    // Convert each account's single, merged unbonding state into a tranche
+    const LEGACY_ACCOUNT_UNBONDINGS: Map<&Addr, AccountUnbondingState> =
+        Map::new("account_unbondings");
+
+    let legacy_unbondings = LEGACY_ACCOUNT_UNBONDINGS
+        .range(store, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for (addr, unbonding) in legacy_unbondings.iter() {
+        ACCOUNT_UNBONDINGS.save(store, addr, &vec![unbonding.to_owned()])?;
+        LEGACY_ACCOUNT_UNBONDINGS.remove(store, addr);
+    }
+
+    // Prior to lock tiers, effective weight was just delegation
+    if TOTAL_WEIGHT.may_load(store)?.is_none() {
+        let total_delegation = TOTAL_DELEGATION.load(store)?;
+        TOTAL_WEIGHT.save(store, &total_delegation)?;
+    }
+
+    // Revenue is now distributed through reward indexes, starting from zero.
+    // Unlike the rest of this migration, existing TS_BALANCE events aren't
+    // converted here. Folding them into every account's sync state takes work
+    // proportional to the number of accounts times their pending events, which
+    // can't be bounded within one transaction. Instead, they remain in place
+    // up to the now frozen SEQ_NO and are folded in incrementally, by each
+    // account's own executions, SyncAccount, the crank and the clock. Accounts
+    // with events left to sync can still stake, since their weight as of the
+    // legacy series is pinned beforehand. Once every account has synced them,
+    // the clock prunes the events along with TS_STAKE, after which the legacy
+    // path no longer does anything and new revenue only touches the indexes.
+    const LEGACY_X: Item<Uint64> = Item::new("x");
+
+    if LEGACY_X.may_load(store)?.is_some() {
+        let mut token_keys = N_BALANCE_EVENTS
+            .keys(store, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        token_keys.extend(
+            BALANCES
+                .keys(store, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?,
+        );
+        token_keys.extend(
+            REVENUE_TOKEN_KEYS
+                .keys(store, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?,
+        );
+
+        for token_key in token_keys.iter() {
+            if !REWARD_INDEXES.has(store, token_key) {
+                REWARD_INDEXES.save(store, token_key, &Uint256::zero())?;
+            }
         }
 
-        TAX_RECIPIENT_INFOS.save(
-            deps.storage,
-            &key,
-            &TaxRecipientInfo {
-                name: info.name.to_owned(),
-                logo: info.logo.to_owned(),
-            },
-        )?;
-        TAX_RECIPIENT_CONFIGS.save(
-            deps.storage,
-            &key,
-            &TaxRecipientConfig {
-                pct: info.pct,
-                autosend: info.autosend,
-                immutable: info.immutable,
-            },
-        )?;
+        LEGACY_X.remove(store);
     }
 
-    Ok(Response::new().add_attribute("action", "instantiate"))
+    // Existing accounts aren't indexed by delegation here, since there may be
+    // too many to save within one transaction. Instead, each is indexed the
+    // next time it's saved, which the crank does as it cycles through them.
+
+    Ok(())
+}
+
+/// Save a tax recipient's info and config, returning its validated address
+pub fn save_tax_recipient(
+    store: &mut dyn Storage,
+    api: &dyn Api,
+    args: &TaxRecipientInitArgs,
+) -> Result<Addr, ContractError> {
+    let key = api.addr_validate(args.address.as_str())?;
+
+    TAX_RECIPIENT_INFOS.save(
+        store,
+        &key,
+        &TaxRecipientInfo {
+            name: args.name.to_owned(),
+            logo: args.logo.to_owned(),
+        },
+    )?;
+    TAX_RECIPIENT_CONFIGS.save(
+        store,
+        &key,
+        &TaxRecipientConfig {
+            pct: args.pct,
+            autosend: args.autosend,
+            immutable: args.immutable,
+        },
+    )?;
+
+    Ok(key)
+}
+
+/// Ensure the aggregate tax rate across all recipients doesn't exceed 100%
+pub fn validate_tax_pct_total(store: &dyn Storage) -> Result<Uint128, ContractError> {
+    let mut total_tax_pct = Uint128::zero();
+    for result in TAX_RECIPIENT_CONFIGS.range(store, None, None, Order::Ascending) {
+        let (_, config) = result?;
+        total_tax_pct = add_u128(total_tax_pct, config.pct)?;
+    }
+    if total_tax_pct > Uint128::from(1_000_000u128) {
+        return Err(ContractError::ValidationError {
+            reason: "aggregate tax rate cannot exceed 1000000 or 100%".to_owned(),
+        });
+    }
+    Ok(total_tax_pct)
 }
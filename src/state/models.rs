@@ -1,9 +1,14 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Timestamp, Uint128, Uint64};
+use cosmwasm_std::{Addr, StdError, Timestamp, Uint128, Uint256, Uint512, Uint64};
 
 use crate::{
     error::ContractError,
-    math::{add_u128, sub_u128},
+    math::{
+        add_u128, add_u32, div_u512, mul_ratio_u128, mul_u512, sub_u128, sum_u128, u256_to_u128,
+        u512_to_u256,
+    },
+    msg::{DepositorCategory, DepositorInfo},
+    sync::{INDEX_PRECISION, SCALE_REBASE_FACTOR},
     token::{Token, TokenAmount},
 };
 
@@ -11,21 +16,20 @@ use crate::{
 pub struct BalanceEvent {
     /// Amount received
     pub delta: Uint128,
-    /// Total delegation at event time
+    /// Total effective weight at event time
     pub total: Uint128,
     /// Number of accounts
     pub n_accounts: u32,
     /// Reference count for garbage collection
     pub ref_count: u32,
-    /// Value used to determine if new event is needed
-    /// or if this one can simply be updated. Compared
-    /// with the global X storage var.
+    /// Value formerly used to determine if new event was needed
+    /// or if this one could simply be updated.
     pub x: Uint64,
 }
 
 #[cw_serde]
 pub struct StakingEvent {
-    /// Delegation increment received at time of event
+    /// Effective weight of the account as of the event
     pub delta: Uint128,
 }
 
@@ -34,6 +38,13 @@ pub struct AccountSyncState {
     pub t: Timestamp,
     pub seq_no: Uint64,
     pub amount: Uint128,
+    /// Value of the token's reward index as of the last sync
+    #[serde(default)]
+    pub index: Uint256,
+    /// Exponent of the delegation scale as of the last sync, i.e. the epoch of
+    /// the reward index
+    #[serde(default)]
+    pub index_exponent: u32,
 }
 
 #[cw_serde]
@@ -80,6 +91,14 @@ pub struct TaxRecipient {
 pub struct Depositor {
     pub address: Addr,
     pub totals: Vec<DepositTokenAmount>,
+    /// Allowlist entry, if any
+    pub info: Option<DepositorInfo>,
+}
+
+#[cw_serde]
+pub struct DepositCategoryTotals {
+    pub category: DepositorCategory,
+    pub totals: Vec<DepositTokenAmount>,
 }
 
 #[cw_serde]
@@ -88,15 +107,175 @@ pub struct AccountUnbondingState {
     pub unbonds_at: Timestamp,
 }
 
+#[cw_serde]
+pub struct PendingManager {
+    pub address: Addr,
+    pub proposed_at: Timestamp,
+    pub expires_at: Option<Timestamp>,
+}
+
+#[cw_serde]
+pub struct StakeLock {
+    pub amount: Uint128,
+    /// Revenue weight multiplier out of 1000000
+    pub multiplier: Uint128,
+    pub unlocks_at: Timestamp,
+}
+
+#[cw_serde]
+pub struct LegacyPruneState {
+    /// Last account visited by the current sweep
+    pub last_address: Option<Addr>,
+    /// Lowest seq_no from which any account visited by the current sweep has
+    /// yet to sync legacy events
+    pub min_seq_no: Uint64,
+    /// Value of min_seq_no as of the last completed sweep. Balance events
+    /// before it aren't needed by any account.
+    pub watermark: Uint64,
+}
+
+/// Summary of the last crank or amortization that synced any accounts
+#[cw_serde]
+pub struct LastCrank {
+    pub height: u64,
+    pub time: Timestamp,
+    pub n_synced: u32,
+}
+
+#[cw_serde]
+pub struct PayoutWindow {
+    pub started_at: Timestamp,
+    pub amount: Uint128,
+}
+
 #[cw_serde]
 pub struct Account {
     pub created_at: Timestamp,
     pub created_at_seq_no: Uint64,
     pub delegation: Uint128,
+    /// Locked portions of delegation, which can't be unstaked until unlocked
+    #[serde(default)]
+    pub locks: Vec<StakeLock>,
+    /// Restake staking token revenue automatically when staking or claiming
+    #[serde(default)]
+    pub auto_compound: bool,
+    /// Value of DELEGATION_SCALE as of the last time delegation was rescaled
+    #[serde(default)]
+    pub scale: Scale,
+}
+
+/// Factor by which amounts have been reduced pro-rata, equal to value divided
+/// by SCALE_REBASE_FACTOR to the power of exponent, scaled by INDEX_PRECISION.
+/// Whenever value falls below INDEX_PRECISION, it's rebased by multiplying it
+/// by SCALE_REBASE_FACTOR and incrementing exponent, so that the scale can be
+/// reduced indefinitely without losing precision.
+#[cw_serde]
+pub struct Scale {
+    pub value: Uint256,
+    pub exponent: u32,
+}
+
+impl Default for Scale {
+    fn default() -> Self {
+        Self {
+            value: Uint256::from(INDEX_PRECISION),
+            exponent: 0,
+        }
+    }
+}
+
+impl Scale {
+    /// Scale reduced by the ratio of numerator to denominator, rebased as
+    /// many times as needed. The numerator must be nonzero.
+    pub fn reduce(
+        &self,
+        numerator: Uint128,
+        denominator: Uint128,
+    ) -> Result<Self, ContractError> {
+        let min_value = mul_u512(denominator, INDEX_PRECISION)?;
+        let mut value = mul_u512(self.value, numerator)?;
+        let mut exponent = self.exponent;
+
+        if value.is_zero() {
+            return Err(ContractError::ValidationError {
+                reason: "scale cannot be reduced to zero".to_owned(),
+            });
+        }
+
+        while value < min_value {
+            value = mul_u512(value, SCALE_REBASE_FACTOR)?;
+            exponent = add_u32(exponent, 1)?;
+        }
+
+        Ok(Self {
+            value: u512_to_u256(div_u512(value, denominator)?)?,
+            exponent,
+        })
+    }
+
+    /// Amount expressed at the given scale, expressed at this scale instead
+    pub fn rescale_amount(
+        &self,
+        amount: Uint128,
+        from: &Scale,
+    ) -> Result<Uint128, ContractError> {
+        u256_to_u128(scale_ratio(
+            amount.into(),
+            self.value,
+            from.exponent,
+            from.value,
+            self.exponent,
+        )?)
+    }
+
+    /// Revenue accrued by weight expressed at this scale, given the increase
+    /// in a reward index during the epoch of the given exponent
+    pub fn accrue(
+        &self,
+        weight: Uint128,
+        index_delta: Uint256,
+        exponent: u32,
+    ) -> Result<Uint128, ContractError> {
+        u256_to_u128(scale_ratio(
+            weight.into(),
+            index_delta,
+            self.exponent,
+            self.value,
+            exponent,
+        )?)
+    }
 }
 
-impl StakingEvent {
-    pub fn default() -> Self {
+/// Compute a * b * F^a_exponent / (c * F^c_exponent), where F is
+/// SCALE_REBASE_FACTOR. The result is zero if the denominator overflows.
+fn scale_ratio(
+    a: Uint256,
+    b: Uint256,
+    a_exponent: u32,
+    c: Uint256,
+    c_exponent: u32,
+) -> Result<Uint256, ContractError> {
+    let factor = Uint512::from(SCALE_REBASE_FACTOR);
+    let numerator = a.full_mul(b);
+
+    if a_exponent > c_exponent {
+        let power = factor
+            .checked_pow(a_exponent - c_exponent)
+            .map_err(|e| ContractError::Std(StdError::overflow(e)))?;
+        return u512_to_u256(div_u512(mul_u512(numerator, power)?, c)?);
+    }
+
+    match factor
+        .checked_pow(c_exponent - a_exponent)
+        .and_then(|power| power.checked_mul(Uint512::from(c)))
+    {
+        Ok(denominator) => u512_to_u256(div_u512(numerator, denominator)?),
+        Err(_) => Ok(Uint256::zero()),
+    }
+}
+
+impl Default for StakingEvent {
+    fn default() -> Self {
         Self {
             delta: Uint128::zero(),
         }
@@ -107,12 +286,89 @@ impl Account {
     pub fn new(
         time: Timestamp,
         seq_no: Uint64,
+        scale: Scale,
     ) -> Self {
         Self {
             created_at: time,
             delegation: Uint128::zero(),
             created_at_seq_no: seq_no,
+            locks: vec![],
+            auto_compound: false,
+            scale,
+        }
+    }
+
+    /// Effective weight used to distribute revenue, i.e. delegation with
+    /// locked amounts boosted by their multipliers.
+    pub fn weight(&self) -> Result<Uint128, ContractError> {
+        let mut weight = self.delegation;
+        for lock in self.locks.iter() {
+            let boost = sub_u128(lock.multiplier, 1_000_000u128)?;
+            weight = add_u128(weight, mul_ratio_u128(lock.amount, boost, 1_000_000u128)?)?;
+        }
+        Ok(weight)
+    }
+
+    /// Delegation as of the initial delegation scale, by which accounts can
+    /// be compared regardless of when they were last rescaled. Saturates for
+    /// accounts rescaled after too many rebases to be compared with the rest.
+    pub fn normalized_delegation(&self) -> Uint512 {
+        Uint512::from(SCALE_REBASE_FACTOR)
+            .checked_pow(self.scale.exponent)
+            .and_then(|power| power.checked_mul(Uint512::from(INDEX_PRECISION)))
+            .and_then(|power| power.checked_mul(Uint512::from(self.delegation)))
+            .map(|n| n / Uint512::from(self.scale.value))
+            .unwrap_or(Uint512::MAX)
+    }
+
+    /// Delegation not held by any lock
+    pub fn unlocked_delegation(&self) -> Result<Uint128, ContractError> {
+        sub_u128(
+            self.delegation,
+            sum_u128(self.locks.iter().map(|l| l.amount).collect())?,
+        )
+    }
+
+    /// Remove locks that have expired as of the given time, returning true if
+    /// any were removed.
+    pub fn release_expired_locks(
+        &mut self,
+        time: Timestamp,
+    ) -> bool {
+        let n_locks = self.locks.len();
+        self.locks.retain(|l| l.unlocks_at > time);
+        self.locks.len() != n_locks
+    }
+
+    /// Reduce delegation and locked amounts in proportion to the change in
+    /// delegation scale since they were last rescaled, returning true if the
+    /// scale changed.
+    pub fn rescale(
+        &mut self,
+        scale: Scale,
+    ) -> Result<bool, ContractError> {
+        if self.scale == scale {
+            return Ok(false);
+        }
+        self.delegation = scale.rescale_amount(self.delegation, &self.scale)?;
+        for lock in self.locks.iter_mut() {
+            lock.amount = scale.rescale_amount(lock.amount, &self.scale)?;
+        }
+        self.scale = scale;
+        Ok(true)
+    }
+
+    /// Remove the amount from delegation, reducing locked amounts pro-rata
+    pub fn slash(
+        &mut self,
+        amount: Uint128,
+    ) -> Result<(), ContractError> {
+        let delegation = sub_u128(self.delegation, amount)?;
+        for lock in self.locks.iter_mut() {
+            lock.amount = mul_ratio_u128(lock.amount, delegation, self.delegation)?;
         }
+        self.delegation = delegation;
+        Ok(())
     }
 
     pub fn add_delegation(
@@ -1,24 +1,31 @@
-use cosmwasm_std::{Addr, Timestamp, Uint128, Uint64};
-use cw_storage_plus::{Deque, Item, Map};
+use cosmwasm_std::{Addr, Timestamp, Uint128, Uint256, Uint64};
+use cw_storage_plus::{Deque, Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 
 use crate::{
-    msg::HouseMarketingInfo,
+    msg::{
+        AmortizationConfig, DepositorInfo, HouseMarketingInfo, InstantUnstakeConfig, LockTier,
+        PayoutConfig,
+    },
     token::{Token, TokenAmount},
 };
 
 use super::models::{
-    Account, AccountSyncState, AccountUnbondingState, BalanceEvent, DepositTotals, StakingEvent,
-    TaxRecipientBalance, TaxRecipientConfig, TaxRecipientInfo,
+    Account, AccountSyncState, AccountUnbondingState, BalanceEvent, DepositTotals, LastCrank,
+    LegacyPruneState, PayoutWindow, PendingManager, Scale, StakingEvent, TaxRecipientBalance,
+    TaxRecipientConfig, TaxRecipientInfo,
 };
 
 pub type TokenKey = String;
 
 /// Minimum increment by which a user can increase their delegation by staking
-pub const MIN_STAKE_INCREMENT: Item<Addr> = Item::new("min_increment");
+pub const MIN_STAKE_INCREMENT: Item<Uint128> = Item::new("min_increment");
 
 /// Max timeout between unstaking and being able to claim unstaked delegation
 pub const UNBONDING_SECONDS: Item<Uint64> = Item::new("unbonding_seconds");
 
+/// Instant unstake penalty settings. Instant unstake is disabled if absent.
+pub const INSTANT_UNSTAKE_CONFIG: Item<InstantUnstakeConfig> = Item::new("instant_unstake_config");
+
 /// Tax recipient metadata, like name & logo
 pub const TAX_RECIPIENT_INFOS: Map<&Addr, TaxRecipientInfo> = Map::new("tax_recipient_infos");
 
@@ -32,9 +39,25 @@ pub const TAX_TOTAL_BALANCES: Map<&TokenKey, Uint128> = Map::new("tax_total_bala
 pub const TAX_RECIPIENT_TOTALS: Map<(&Addr, &TokenKey), TaxRecipientBalance> =
     Map::new("tax_recipient_totals");
 
+/// Share of each deposit burned instead of going to stakers or taxes, out of
+/// 1000000. Every revenue token must be burnable by the contract if nonzero,
+/// which is enforced when it's set and when revenue tokens are added.
+pub const BURN_PCT: Item<Uint128> = Item::new("burn_pct");
+
 /// Grand total amount of delegated (not unbonding) token across all stakers
 pub const TOTAL_DELEGATION: Item<Uint128> = Item::new("total_delegation");
 
+/// Grand total effective weight, i.e. delegation boosted by lock multipliers,
+/// across all stakers
+pub const TOTAL_WEIGHT: Item<Uint128> = Item::new("total_weight");
+
+/// Factor by which every account's delegation has been reduced by payouts.
+/// Accounts apply it lazily. Scale::default() if absent.
+pub const DELEGATION_SCALE: Item<Scale> = Item::new("delegation_scale");
+
+/// Durations for which stake can be locked and their weight multipliers
+pub const LOCK_TIERS: Item<Vec<LockTier>> = Item::new("lock_tiers");
+
 /// Grand total amount of unbonding token
 pub const TOTAL_UNBONDING: Item<Uint128> = Item::new("total_unbonding");
 
@@ -62,6 +85,9 @@ pub const CREATED_BY: Item<Addr> = Item::new("created_by");
 /// Address of manager contract or wallet
 pub const MANAGED_BY: Item<Addr> = Item::new("managed_by");
 
+/// Proposed new manager, pending acceptance by the proposed address
+pub const PENDING_MANAGER: Item<PendingManager> = Item::new("pending_manager");
+
 /// Aggregate total number of deposits per token type
 pub const N_DEPOSITS: Map<&TokenKey, Uint64> = Map::new("n_deposits");
 
@@ -71,30 +97,107 @@ pub const DEPOSIT_AGG_TOTALS: Map<&TokenKey, DepositTotals> = Map::new("deposit_
 /// Total amount deposited by each depositor for each token type
 pub const DEPOSITOR_TOTALS: Map<(&TokenKey, &Addr), DepositTotals> = Map::new("depositor_totals");
 
-/// A value that is used to determine whether a new TS_BALANCE entry should be
-/// created instead of updating the latest entry.
-pub const X: Item<Uint64> = Item::new("x");
+/// Depositor allowlist entries, also used to categorize deposits
+pub const DEPOSITORS: Map<&Addr, DepositorInfo> = Map::new("depositors");
+
+/// Whether deposits are restricted to allowlisted depositors
+pub const DEPOSITOR_ALLOWLIST_ENABLED: Item<bool> = Item::new("depositor_allowlist_enabled");
+
+/// Total amount deposited by allowlisted depositors of each category for each
+/// token type
+pub const DEPOSIT_CATEGORY_TOTALS: Map<(&str, &TokenKey), DepositTotals> =
+    Map::new("deposit_category_totals");
+
+pub struct AccountIndexes<'a> {
+    /// Accounts by delegation, normalized by their delegation scale so that
+    /// the order isn't affected by payouts and slashing not yet applied
+    pub delegation: MultiIndex<'a, Vec<u8>, Account, &'a Addr>,
+}
+
+impl<'a> IndexList<Account> for AccountIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Account>> + '_> {
+        let v: Vec<&dyn Index<Account>> = vec![&self.delegation];
+        Box::new(v.into_iter())
+    }
+}
 
 /// Storage for staking accounts
-pub const ACCOUNTS: Map<&Addr, Account> = Map::new("accounts");
+pub const ACCOUNTS: IndexedMap<&Addr, Account, AccountIndexes> = IndexedMap::new(
+    "accounts",
+    AccountIndexes {
+        delegation: MultiIndex::new(
+            |_, account| account.normalized_delegation().to_be_bytes().to_vec(),
+            "accounts",
+            "accounts__delegation",
+        ),
+    },
+);
 
 /// State that pertains to the token balances of each staker
 pub const ACCOUNT_SYNC_INFOS: Map<(&Addr, &TokenKey), AccountSyncState> = Map::new("account_syncs");
 
-/// Storage for an account while unbonding via unstake
-pub const ACCOUNT_UNBONDINGS: Map<&Addr, AccountUnbondingState> = Map::new("account_unbondings");
+/// Unbonding tranches of an account, each created by an unstake and maturing
+/// independently
+pub const ACCOUNT_UNBONDINGS: Map<&Addr, Vec<AccountUnbondingState>> =
+    Map::new("account_unbonding_tranches");
+
+/// Factor by which unbonding tranches have been reduced by pool-wide slashing.
+/// Scale::default() if absent.
+pub const UNBONDING_SCALE: Item<Scale> = Item::new("unbonding_scale");
+
+/// Value of UNBONDING_SCALE as of when each account's tranches were saved
+pub const ACCOUNT_UNBONDING_SCALES: Map<&Addr, Scale> = Map::new("account_unbonding_scales");
 
-/// Sequence number used by TS_BALANCE and the sync process
+/// Sequence number marking the end of the legacy TS_BALANCE time series.
+/// Revenue is distributed through REWARD_INDEXES, so it no longer advances.
 pub const SEQ_NO: Item<Uint64> = Item::new("seq_no");
 
-/// Timeseries for balance changes
+/// Legacy timeseries for balance changes, pending sync by accounts
 pub const TS_BALANCE: Map<(&TokenKey, u64), BalanceEvent> = Map::new("ts_balance");
 
-/// Timeseries for changes to accounts' stake
+/// Legacy timeseries for changes to accounts' stake
 pub const TS_STAKE: Map<(&Addr, u64), StakingEvent> = Map::new("ts_stake");
 
+/// Progress of incrementally pruning legacy events no longer needed
+pub const LEGACY_PRUNE_STATE: Item<LegacyPruneState> = Item::new("legacy_prune_state");
+
+/// Cumulative staking revenue per unit of weight for each token type, scaled
+/// by the value of the delegation scale. Restarts from zero whenever the scale
+/// is rebased.
+pub const REWARD_INDEXES: Map<&TokenKey, Uint256> = Map::new("reward_indexes");
+
+/// Final value of each token's reward index in the epoch of each exponent of
+/// the delegation scale, archived when the scale was rebased
+pub const REWARD_INDEX_HISTORY: Map<(&TokenKey, u32), Uint256> = Map::new("reward_index_history");
+
+/// Staking revenue received while nothing was staked, carried forward to the
+/// next deposit of the token type
+pub const UNDISTRIBUTED_REVENUE: Map<&TokenKey, Uint128> = Map::new("undistributed_revenue");
+
 /// Aggregate total balance of each token type currently tracked by the house
 pub const BALANCES: Map<&TokenKey, TokenAmount> = Map::new("balances");
 
 /// A cyclic buffer of account addresses to sync during amortization
 pub const AMORTIZATION_QUEUE: Deque<Addr> = Deque::new("amortization_queue");
+
+/// Closed accounts whose addresses remain in AMORTIZATION_QUEUE until the
+/// next crank reaches them
+pub const CLOSED_QUEUED_ACCOUNTS: Map<&Addr, u8> = Map::new("closed_queued_accounts");
+
+/// Amortization batch settings. DEFAULT_AMORTIZATION_CONFIG is used if absent.
+pub const AMORTIZATION_CONFIG: Item<AmortizationConfig> = Item::new("amortization_config");
+
+/// Block and size of the last amortization that synced any accounts
+pub const LAST_CRANK: Item<LastCrank> = Item::new("last_crank");
+
+/// Game contracts authorized to request payouts
+pub const GAMES: Map<&Addr, u8> = Map::new("games");
+
+/// Payout limits. Payouts are disabled if absent.
+pub const PAYOUT_CONFIG: Item<PayoutConfig> = Item::new("payout_config");
+
+/// Total paid out in the current payout window
+pub const PAYOUT_WINDOW: Item<PayoutWindow> = Item::new("payout_window");
+
+/// Recipient of slashed stake. Slashed stake is burned if absent.
+pub const SLASH_RECIPIENT: Item<Addr> = Item::new("slash_recipient");
@@ -0,0 +1,50 @@
+use cosmwasm_std::{attr, DepsMut, Env, Response};
+
+use crate::{
+    error::ContractError,
+    state::storage::SEQ_NO,
+    sync::{crank, is_legacy_pruned, prune_legacy_events},
+};
+
+// Juno's clock module limits each end block sudo to 100000 gas by default,
+// jailing contracts that exceed it, so the work done per block is kept to a
+// small fraction of what an execution may do.
+
+/// Max number of accounts synced per block
+pub const CLOCK_SYNC_ACCOUNTS: u32 = 1;
+
+/// Max number of legacy events synced per block
+pub const CLOCK_SYNC_EVENTS: u32 = 5;
+
+/// Max number of legacy events or accounts processed by pruning per block
+pub const CLOCK_PRUNE_ITEMS: u32 = 10;
+
+/// Sync queued accounts and prune legacy events no longer needed at the end
+/// of each block, until every legacy event is gone. Regular amortization
+/// still cranks accounts afterwards, so the clock does nothing more.
+pub fn sudo_clock_end_block(
+    deps: DepsMut,
+    env: Env,
+) -> Result<Response, ContractError> {
+    if is_legacy_pruned(deps.storage)? {
+        return Ok(Response::new().add_attribute("action", "clock_end_block"));
+    }
+
+    let seq_no = SEQ_NO.load(deps.storage)?;
+
+    let n_synced = crank(
+        deps.storage,
+        &env.block,
+        seq_no,
+        None,
+        CLOCK_SYNC_ACCOUNTS,
+        CLOCK_SYNC_EVENTS,
+    )?;
+    let n_pruned = prune_legacy_events(deps.storage, CLOCK_PRUNE_ITEMS)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "clock_end_block"),
+        attr("n_synced", n_synced.to_string()),
+        attr("n_pruned", n_pruned.to_string()),
+    ]))
+}
@@ -1,73 +1,466 @@
 use std::marker::PhantomData;
 
-use cosmwasm_std::{Addr, Api, Order, Storage, Uint128, Uint64};
+use cosmwasm_std::{Addr, BlockInfo, Order, StdResult, Storage, Uint128, Uint256, Uint64};
 use cw_storage_plus::Bound;
 
 use crate::{
     error::ContractError,
-    math::{add_u128, mul_ratio_u128, mul_u32, sub_u32},
+    math::{
+        add_u128, add_u256, div_u128, div_u256, mul_ratio_u128, mul_u128, mul_u256, sub_u128,
+        sub_u256, sub_u32, u256_to_u128,
+    },
+    msg::AmortizationConfig,
     state::{
-        models::{Account, AccountSyncState, BalanceEvent, StakingEvent},
+        models::{
+            Account, AccountSyncState, AccountUnbondingState, BalanceEvent, LastCrank,
+            LegacyPruneState, Scale, StakingEvent,
+        },
         storage::{
-            ACCOUNTS, ACCOUNT_SYNC_INFOS, AMORTIZATION_QUEUE, N_ACCOUNTS, N_BALANCE_EVENTS,
-            TS_BALANCE, TS_STAKE,
+            ACCOUNTS, ACCOUNT_SYNC_INFOS, ACCOUNT_UNBONDINGS, ACCOUNT_UNBONDING_SCALES,
+            AMORTIZATION_CONFIG, AMORTIZATION_QUEUE, CLOSED_QUEUED_ACCOUNTS, DELEGATION_SCALE,
+            LAST_CRANK, LEGACY_PRUNE_STATE, N_ACCOUNTS, N_BALANCE_EVENTS, REWARD_INDEXES,
+            REWARD_INDEX_HISTORY, SEQ_NO, TOTAL_DELEGATION, TOTAL_UNBONDING, TOTAL_WEIGHT,
+            TS_BALANCE, TS_STAKE, UNBONDING_SCALE, UNDISTRIBUTED_REVENUE,
         },
     },
     token::Token,
 };
 
+/// Scale of reward indexes, i.e. revenue per unit of weight, and of the
+/// delegation scale
+pub const INDEX_PRECISION: u128 = 1_000_000_000_000_000_000;
+
+/// Factor by which the value of a scale is multiplied when rebased
+pub const SCALE_REBASE_FACTOR: u128 = 1_000_000_000;
+
+/// Max number of legacy events an account syncs as part of another action
+pub const MAX_IMPLICIT_SYNC_EVENTS: u32 = 100;
+
 pub struct TokenSyncResult {
     pub token: Token,
     pub updated_balance_events: Vec<((String, u64), BalanceEvent)>,
     pub zombie_balance_event_keys: Vec<(String, u64)>,
-    pub zombie_delegation_event_keys: Vec<u64>,
+    /// True if legacy events were synced through the end of the series
+    pub legacy_complete: bool,
+    /// Number of legacy events synced
+    pub n_legacy_events: u32,
+    /// Seq_no of the next legacy event to sync
+    pub legacy_cursor: Uint64,
     pub synced_amount: Uint128,
 }
 
+impl TokenSyncResult {
+    /// Result for a token with no legacy events to sync
+    pub fn empty(token: &Token) -> Self {
+        Self {
+            token: token.to_owned(),
+            updated_balance_events: vec![],
+            zombie_balance_event_keys: vec![],
+            legacy_complete: false,
+            n_legacy_events: 0,
+            legacy_cursor: Uint64::zero(),
+            synced_amount: Uint128::zero(),
+        }
+    }
+}
+
+/// Amortization settings used until the manager configures them
+pub const DEFAULT_AMORTIZATION_CONFIG: AmortizationConfig = AmortizationConfig {
+    batch_size: 10,
+    account_pct: Uint128::new(50_000),
+};
+
+/// Max number of accounts that can be synced by a single crank
+pub const MAX_CRANK_LIMIT: u32 = 100;
+
+/// Sync a batch of queued accounts, sized according to the amortization
+/// config. The share of all accounts is rounded up so that small houses are
+/// amortized too.
 pub fn amortize(
     store: &mut dyn Storage,
-    api: &dyn Api,
+    block: &BlockInfo,
     seq_no: Uint64,
-    token: Option<Token>,
     ignore_address: Option<Addr>,
-) -> Result<(), ContractError> {
+) -> Result<u32, ContractError> {
+    let config = AMORTIZATION_CONFIG
+        .may_load(store)?
+        .unwrap_or(DEFAULT_AMORTIZATION_CONFIG);
     let n_accounts = N_ACCOUNTS.load(store)?;
+
+    let share = div_u128(
+        add_u128(mul_u128(n_accounts, config.account_pct)?, 999_999u128)?,
+        1_000_000u128,
+    )?;
+    let n = config
+        .batch_size
+        .min(u32::try_from(share.u128()).unwrap_or(u32::MAX));
+
+    crank(
+        store,
+        block,
+        seq_no,
+        ignore_address,
+        n,
+        MAX_IMPLICIT_SYNC_EVENTS,
+    )
+}
+
+/// Sync up to n accounts at the front of the amortization queue, moving each
+/// to the back. Each account is rescaled and has its expired locks released,
+/// so that dormant accounts don't keep boosted weight indefinitely. At most
+/// max_events legacy events are synced across the whole batch, so accounts
+/// after the budget runs out only sync their reward indexes. Returns the
+/// number of accounts synced.
+pub fn crank(
+    store: &mut dyn Storage,
+    block: &BlockInfo,
+    seq_no: Uint64,
+    ignore_address: Option<Addr>,
+    n: u32,
+    max_events: u32,
+) -> Result<u32, ContractError> {
     let queue_size = AMORTIZATION_QUEUE.len(store)?;
+    let mut n_synced: u32 = 0;
+    let mut remaining_events = max_events;
 
-    if queue_size > 0 {
-        let n = 10
-            .min(queue_size)
-            .min((mul_u32(n_accounts, 50_000)?) / 1_000_000);
-
-        for _ in 0..n {
-            if let Some(addr) = AMORTIZATION_QUEUE.pop_front(store)? {
-                if let Some(ignore_addr) = &ignore_address {
-                    if addr == *ignore_addr {
-                        AMORTIZATION_QUEUE.push_back(store, &addr)?;
-                        continue;
-                    }
-                }
-                if let Some(account) = ACCOUNTS.may_load(store, &addr)? {
-                    let results =
-                        sync_account(store, api, &addr, &account, seq_no, token.to_owned(), false)?;
-                    for (result, state) in results.iter() {
-                        persist_sync_results(store, &addr, result, state)?;
-                    }
+    for _ in 0..n.min(queue_size) {
+        if let Some(addr) = AMORTIZATION_QUEUE.pop_front(store)? {
+            if let Some(ignore_addr) = &ignore_address {
+                if addr == *ignore_addr {
                     AMORTIZATION_QUEUE.push_back(store, &addr)?;
+                    continue;
+                }
+            }
+            if let Some(mut account) = ACCOUNTS.may_load(store, &addr)? {
+                // Sync every token, since the account's weight may change
+                let results =
+                    sync_account(store, &addr, &account, seq_no, None, Some(remaining_events))?;
+                for (result, _) in results.iter() {
+                    remaining_events = remaining_events.saturating_sub(result.n_legacy_events);
+                }
+                pin_legacy_weight(store, &addr, &account, &results, seq_no)?;
+                for (result, state) in results.iter() {
+                    persist_sync_results(store, &addr, result, state)?;
                 }
+
+                account.rescale(load_delegation_scale(store)?)?;
+
+                let prev_weight = account.weight()?;
+                if account.release_expired_locks(block.time) {
+                    update_account_weight(store, prev_weight, account.weight()?)?;
+                }
+                ACCOUNTS.save(store, &addr, &account)?;
+
+                AMORTIZATION_QUEUE.push_back(store, &addr)?;
+                n_synced += 1;
+            } else {
+                // Drop closed accounts from the queue
+                CLOSED_QUEUED_ACCOUNTS.remove(store, &addr);
             }
         }
     }
+
+    if n_synced > 0 {
+        LAST_CRANK.save(
+            store,
+            &LastCrank {
+                height: block.height,
+                time: block.time,
+                n_synced,
+            },
+        )?;
+    }
+
+    Ok(n_synced)
+}
+
+/// True once every legacy event has been synced by every account and pruned
+pub fn is_legacy_pruned(store: &dyn Storage) -> Result<bool, ContractError> {
+    for n in N_BALANCE_EVENTS
+        .range(store, None, None, Order::Ascending)
+        .map(|r| r.map(|(_, n)| n))
+    {
+        if n? > 0 {
+            return Ok(false);
+        }
+    }
+    Ok(TS_STAKE
+        .keys_raw(store, None, None, Order::Ascending)
+        .next()
+        .is_none())
+}
+
+/// Incrementally delete legacy events that no account still needs, processing
+/// at most limit items. Accounts are swept in batches to find the lowest
+/// seq_no from which any account has yet to sync. Once a sweep completes,
+/// balance events before that seq_no are deleted. Delegation events are
+/// deleted while sweeping accounts that have synced every legacy event.
+/// Returns the number of items processed.
+pub fn prune_legacy_events(
+    store: &mut dyn Storage,
+    limit: u32,
+) -> Result<u32, ContractError> {
+    let seq_no = SEQ_NO.load(store)?;
+    let mut state = LEGACY_PRUNE_STATE
+        .may_load(store)?
+        .unwrap_or(LegacyPruneState {
+            last_address: None,
+            min_seq_no: seq_no,
+            watermark: Uint64::zero(),
+        });
+
+    let mut n: u32 = 0;
+
+    // Delete balance events before the watermark of the last completed sweep
+    for token_key in N_BALANCE_EVENTS
+        .keys(store, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?
+    {
+        let keys = TS_BALANCE
+            .prefix(&token_key)
+            .keys(
+                store,
+                None,
+                Some(Bound::exclusive(state.watermark.u64())),
+                Order::Ascending,
+            )
+            .take((limit - n) as usize)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        for key in keys.iter() {
+            TS_BALANCE.remove(store, (&token_key, *key));
+        }
+
+        N_BALANCE_EVENTS.update(store, &token_key, |maybe_n| -> Result<_, ContractError> {
+            Ok(maybe_n
+                .unwrap_or_default()
+                .saturating_sub(keys.len() as u32))
+        })?;
+
+        n += keys.len() as u32;
+    }
+
+    // Continue the sweep of accounts with whatever remains of the limit
+    let remaining = limit - n;
+    let tokens = load_reward_tokens(store)?;
+    let accounts = ACCOUNTS
+        .range(
+            store,
+            state.last_address.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(remaining as usize)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for (addr, account) in accounts.iter() {
+        let mut account_seq_no = seq_no;
+        for token in tokens.iter() {
+            account_seq_no =
+                account_seq_no.min(load_sync_state(store, addr, account, token)?.seq_no);
+        }
+
+        if account_seq_no >= seq_no {
+            for key in TS_STAKE
+                .prefix(addr)
+                .keys(store, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?
+            {
+                TS_STAKE.remove(store, (addr, key));
+            }
+        }
+
+        state.min_seq_no = state.min_seq_no.min(account_seq_no);
+        state.last_address = Some(addr.to_owned());
+    }
+
+    n += accounts.len() as u32;
+
+    // Complete the sweep if the last account was visited
+    if remaining > 0 && (accounts.len() as u32) < remaining {
+        state.watermark = state.min_seq_no;
+        state.min_seq_no = seq_no;
+        state.last_address = None;
+    }
+
+    LEGACY_PRUNE_STATE.save(store, &state)?;
+
+    Ok(n)
+}
+
+/// Distribute staking revenue to all stakers by incrementing the token's
+/// reward index in proportion to total weight. Revenue received while nothing
+/// is staked is carried forward to the next distribution.
+///
+/// The increment is multiplied by the value of the delegation scale so that
+/// accounts can accrue with respect to their weight as of their last rescale,
+/// dividing by the scale at the time.
+pub fn distribute_revenue(
+    store: &mut dyn Storage,
+    token_key: &String,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let total_weight = TOTAL_WEIGHT.load(store)?;
+    let scale = load_delegation_scale(store)?;
+    let amount = add_u128(
+        amount,
+        UNDISTRIBUTED_REVENUE
+            .may_load(store, token_key)?
+            .unwrap_or_default(),
+    )?;
+
+    let mut index = REWARD_INDEXES
+        .may_load(store, token_key)?
+        .unwrap_or_default();
+
+    if total_weight.is_zero() {
+        UNDISTRIBUTED_REVENUE.save(store, token_key, &amount)?;
+    } else {
+        UNDISTRIBUTED_REVENUE.remove(store, token_key);
+        index = add_u256(
+            index,
+            div_u256(mul_u256(amount, scale.value)?, total_weight)?,
+        )?;
+    }
+
+    REWARD_INDEXES.save(store, token_key, &index)?;
+
     Ok(())
 }
 
+/// Record a change in an account's effective weight by adjusting the total
+/// weight. The account must be synced and rescaled beforehand. Since rescaled
+/// weights are rounded independently of the total, the total saturates at 0.
+pub fn update_account_weight(
+    store: &mut dyn Storage,
+    prev_weight: Uint128,
+    weight: Uint128,
+) -> Result<(), ContractError> {
+    TOTAL_WEIGHT.update(store, |total| -> Result<_, ContractError> {
+        Ok(add_u128(total, weight)?.saturating_sub(prev_weight))
+    })?;
+    Ok(())
+}
+
+pub fn load_delegation_scale(store: &dyn Storage) -> Result<Scale, ContractError> {
+    Ok(DELEGATION_SCALE.may_load(store)?.unwrap_or_default())
+}
+
+pub fn load_unbonding_scale(store: &dyn Storage) -> Result<Scale, ContractError> {
+    Ok(UNBONDING_SCALE.may_load(store)?.unwrap_or_default())
+}
+
+/// Load the account's unbonding tranches, reduced by any slashing since they
+/// were saved. Tranches reduced to nothing are dropped.
+pub fn load_unbondings(
+    store: &dyn Storage,
+    address: &Addr,
+) -> Result<Vec<AccountUnbondingState>, ContractError> {
+    let scale = load_unbonding_scale(store)?;
+    let prev_scale = ACCOUNT_UNBONDING_SCALES
+        .may_load(store, address)?
+        .unwrap_or_default();
+
+    let mut tranches = ACCOUNT_UNBONDINGS
+        .may_load(store, address)?
+        .unwrap_or_default();
+
+    if scale != prev_scale {
+        for tranche in tranches.iter_mut() {
+            tranche.amount = scale.rescale_amount(tranche.amount, &prev_scale)?;
+        }
+        tranches.retain(|t| !t.amount.is_zero());
+    }
+
+    Ok(tranches)
+}
+
+/// Save the account's unbonding tranches as of the current unbonding scale
+pub fn save_unbondings(
+    store: &mut dyn Storage,
+    address: &Addr,
+    tranches: &Vec<AccountUnbondingState>,
+) -> Result<(), ContractError> {
+    if tranches.is_empty() {
+        ACCOUNT_UNBONDINGS.remove(store, address);
+        ACCOUNT_UNBONDING_SCALES.remove(store, address);
+    } else {
+        let scale = load_unbonding_scale(store)?;
+        ACCOUNT_UNBONDINGS.save(store, address, tranches)?;
+        ACCOUNT_UNBONDING_SCALES.save(store, address, &scale)?;
+    }
+    Ok(())
+}
+
+/// Reduce every unbonding tranche by the given rate out of 1000000 by scaling
+/// them down. Returns the amount removed from total unbonding.
+pub fn reduce_unbonding(
+    store: &mut dyn Storage,
+    pct: Uint128,
+) -> Result<Uint128, ContractError> {
+    let total_unbonding = TOTAL_UNBONDING.load(store)?;
+    let amount = mul_ratio_u128(total_unbonding, pct, 1_000_000u128)?;
+    let scale = load_unbonding_scale(store)?
+        .reduce(sub_u128(1_000_000u128, pct)?, Uint128::from(1_000_000u128))?;
+
+    TOTAL_UNBONDING.save(store, &sub_u128(total_unbonding, amount)?)?;
+    UNBONDING_SCALE.save(store, &scale)?;
+
+    Ok(amount)
+}
+
+/// Remove the amount from total delegation, reducing every account's
+/// delegation pro-rata by scaling it down. Total weight is scaled likewise,
+/// rounding up. If the scale is rebased, reward indexes are archived and
+/// restarted from zero. Returns the new delegation scale.
+pub fn reduce_delegation(
+    store: &mut dyn Storage,
+    amount: Uint128,
+) -> Result<Scale, ContractError> {
+    let total_delegation = TOTAL_DELEGATION.load(store)?;
+    let remaining = sub_u128(total_delegation, amount)?;
+
+    if remaining.is_zero() {
+        return Err(ContractError::ValidationError {
+            reason: "amount cannot exhaust total delegation".to_owned(),
+        });
+    }
+
+    let prev_scale = load_delegation_scale(store)?;
+    let scale = prev_scale.reduce(remaining, total_delegation)?;
+
+    if scale.exponent != prev_scale.exponent {
+        for (token_key, index) in REWARD_INDEXES
+            .range(store, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?
+        {
+            REWARD_INDEX_HISTORY.save(store, (&token_key, prev_scale.exponent), &index)?;
+            REWARD_INDEXES.save(store, &token_key, &Uint256::zero())?;
+        }
+    }
+
+    TOTAL_WEIGHT.update(store, |total| -> Result<_, ContractError> {
+        u256_to_u128(div_u256(
+            add_u256(
+                mul_u256(total, remaining)?,
+                sub_u128(total_delegation, 1u128)?,
+            )?,
+            total_delegation,
+        )?)
+    })?;
+    TOTAL_DELEGATION.save(store, &remaining)?;
+    DELEGATION_SCALE.save(store, &scale)?;
+
+    Ok(scale)
+}
+
 pub fn persist_sync_results(
     store: &mut dyn Storage,
     staker: &Addr,
     result: &TokenSyncResult,
     sync_state: &AccountSyncState,
 ) -> Result<(), ContractError> {
-    ACCOUNT_SYNC_INFOS.save(store, (&staker, &result.token.to_key()), sync_state)?;
+    ACCOUNT_SYNC_INFOS.save(store, (staker, &result.token.to_key()), sync_state)?;
 
     for ((a, b), v) in result.updated_balance_events.iter() {
         TS_BALANCE.save(store, (a, *b), v)?;
@@ -89,173 +482,310 @@ pub fn persist_sync_results(
         TS_BALANCE.remove(store, (token_key, *seq_no));
     }
 
-    for seq_no in result.zombie_delegation_event_keys.iter() {
-        TS_STAKE.remove(store, (staker, *seq_no));
+    if result.legacy_complete {
+        prune_legacy_delegation_events(store, staker, sync_state.seq_no)?;
     }
 
     Ok(())
 }
 
+/// Tokens for which revenue has been distributed through a reward index
+pub fn load_reward_tokens(store: &dyn Storage) -> Result<Vec<Token>, ContractError> {
+    Ok(REWARD_INDEXES
+        .keys(store, None, None, Order::Ascending)
+        .map(|r| r.map(|k| Token::from_key(&k)))
+        .collect::<StdResult<Vec<_>>>()?)
+}
+
 pub fn sync_account(
     store: &dyn Storage,
-    api: &dyn Api,
     address: &Addr,
     account: &Account,
     seq_no: Uint64,
     token: Option<Token>,
-    terminal: bool,
+    max_events: Option<u32>,
 ) -> Result<Vec<(TokenSyncResult, AccountSyncState)>, ContractError> {
-    let mut retval: Vec<(TokenSyncResult, AccountSyncState)> = Vec::with_capacity(2);
-    if let Some(token) = token {
-        let mut sync_state = ACCOUNT_SYNC_INFOS
-            .may_load(store, (address, &token.to_key()))?
-            .or_else(|| {
-                Some(AccountSyncState {
-                    amount: Uint128::zero(),
-                    seq_no: account.created_at_seq_no,
-                    t: account.created_at,
-                })
-            })
-            .unwrap();
-        if let Some(result) = sync_account_balance(
+    // Sync the given token or else every token with a reward index
+    let tokens: Vec<Token> = if let Some(token) = token {
+        vec![token]
+    } else {
+        load_reward_tokens(store)?
+    };
+
+    let mut retval: Vec<(TokenSyncResult, AccountSyncState)> = Vec::with_capacity(tokens.len());
+
+    // Remaining number of legacy events that can be synced across all tokens
+    let mut remaining_events = max_events;
+
+    for token in tokens.iter() {
+        let mut sync_state = load_sync_state(store, address, account, token)?;
+
+        let result = sync_account_balance(
             store,
-            api,
-            &address,
+            address,
+            account,
             &mut sync_state,
-            &token,
+            token,
             seq_no,
-            terminal,
-        )? {
-            retval.push((result, sync_state));
-        }
-    } else {
-        for result in ACCOUNT_SYNC_INFOS
-            .prefix(address)
-            .range(store, None, None, Order::Ascending)
-        {
-            let (token_key, mut sync_state) = result?;
-            let token = Token::from_key(&token_key);
-            if let Some(result) = sync_account_balance(
-                store,
-                api,
-                &address,
-                &mut sync_state,
-                &token,
-                seq_no,
-                terminal,
-            )? {
-                retval.push((result, sync_state));
-            }
-        }
+            remaining_events,
+        )?;
+
+        remaining_events = remaining_events.map(|n| n.saturating_sub(result.n_legacy_events));
+        retval.push((result, sync_state));
     }
+
     Ok(retval)
 }
 
+/// Load the account's sync state for the given token, defaulting to the state
+/// at account creation if the token has never been synced.
+pub fn load_sync_state(
+    store: &dyn Storage,
+    address: &Addr,
+    account: &Account,
+    token: &Token,
+) -> Result<AccountSyncState, ContractError> {
+    Ok(ACCOUNT_SYNC_INFOS
+        .may_load(store, (address, &token.to_key()))?
+        .unwrap_or_else(|| AccountSyncState {
+            amount: Uint128::zero(),
+            seq_no: account.created_at_seq_no,
+            t: account.created_at,
+            index: Uint256::zero(),
+            index_exponent: account.scale.exponent,
+        }))
+}
+
+/// Ensure that every sync result reached the end of the legacy event series,
+/// so that nothing is left out of a claim or an unstake.
+pub fn ensure_legacy_synced(
+    results: &[(TokenSyncResult, AccountSyncState)],
+    seq_no: Uint64,
+) -> Result<(), ContractError> {
+    if results.iter().any(|(_, state)| state.seq_no < seq_no) {
+        return Err(ContractError::SyncRequired {
+            max_events: MAX_IMPLICIT_SYNC_EVENTS,
+        });
+    }
+    Ok(())
+}
+
+/// Record the account's current weight as of the first legacy event it has
+/// yet to sync, before its weight changes. Legacy events are synced with
+/// respect to the account's delegation events at the time, falling back to
+/// its current weight if it has none, so pinning the weight lets it change
+/// without waiting for the account to sync every legacy event.
+pub fn pin_legacy_weight(
+    store: &mut dyn Storage,
+    address: &Addr,
+    account: &Account,
+    results: &[(TokenSyncResult, AccountSyncState)],
+    seq_no: Uint64,
+) -> Result<(), ContractError> {
+    let cursor = results
+        .iter()
+        .map(|(_, state)| state.seq_no)
+        .min()
+        .unwrap_or(seq_no);
+
+    if cursor < seq_no
+        && TS_STAKE
+            .prefix(address)
+            .keys(store, None, None, Order::Ascending)
+            .next()
+            .is_none()
+    {
+        TS_STAKE.save(
+            store,
+            (address, cursor.u64()),
+            &StakingEvent {
+                delta: account.weight()?,
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Accrue the account's revenue since its last sync, first from up to
+/// max_events legacy events before the given seq_no and then from the token's
+/// reward index.
 pub fn sync_account_balance(
     store: &dyn Storage,
-    api: &dyn Api,
     delegator: &Addr,
+    account: &Account,
     sync: &mut AccountSyncState,
     token: &Token,
     seq_no: Uint64,
-    terminal: bool,
-) -> Result<Option<TokenSyncResult>, ContractError> {
-    // Compute delegator account's aggregate synced revenue amount
-    if let Some(result) = perform_sync(store, api, delegator, sync, token, seq_no, terminal)? {
-        // Update the sync state of the delegator's account
-        sync.amount = add_u128(sync.amount, result.synced_amount)?;
-        sync.seq_no = seq_no;
-        return Ok(Some(result));
+    max_events: Option<u32>,
+) -> Result<TokenSyncResult, ContractError> {
+    let mut result =
+        sync_legacy_events(store, delegator, account, sync, token, seq_no, max_events)?;
+
+    // Accrue revenue distributed since the account's last sync, during which
+    // its weight as of its last rescale has been constant. The reward index
+    // of each epoch since then is accrued separately, ending with the current
+    // one, since indexes restart from zero whenever the scale is rebased.
+    let token_key = token.to_key();
+    let weight = account.weight()?;
+    let exponent = load_delegation_scale(store)?.exponent;
+    let index = REWARD_INDEXES
+        .may_load(store, &token_key)?
+        .unwrap_or_default();
+
+    let mut prev_index = sync.index;
+    for epoch in sync.index_exponent..=exponent {
+        let end_index = if epoch == exponent {
+            index
+        } else {
+            REWARD_INDEX_HISTORY
+                .may_load(store, (&token_key, epoch))?
+                .unwrap_or_default()
+        };
+        let accrued = account
+            .scale
+            .accrue(weight, sub_u256(end_index, prev_index)?, epoch)?;
+        result.synced_amount = add_u128(result.synced_amount, accrued)?;
+        prev_index = Uint256::zero();
     }
 
-    Ok(None)
+    // Update the sync state of the delegator's account
+    sync.amount = add_u128(sync.amount, result.synced_amount)?;
+    sync.seq_no = sync.seq_no.max(result.legacy_cursor);
+    sync.index = index;
+    sync.index_exponent = exponent;
+
+    Ok(result)
 }
 
-fn perform_sync(
+/// Compute the account's share of legacy TS_BALANCE events, which predate
+/// reward indexes, between its sync state's seq_no and the given seq_no. If
+/// there are more than max_events, the result's cursor is the seq_no of the
+/// first event left to sync.
+fn sync_legacy_events(
     store: &dyn Storage,
-    api: &dyn Api,
     delegator: &Addr,
+    account: &Account,
     sync_state: &AccountSyncState,
     token: &Token,
     seq_no: Uint64,
-    _terminal: bool,
-) -> Result<Option<TokenSyncResult>, ContractError> {
-    let mut delegation_events = load_delegation_events(store, &delegator, sync_state, seq_no)?;
-    let mut agg_sync_amount = Uint128::zero();
-
-    api.debug(format!("token: {:?}", token).as_str());
-    api.debug(format!("seq_no: {:?}", seq_no).as_str());
-    api.debug(format!("initial sync state: {:?}", sync_state).as_str());
-    api.debug(format!("delegation events: {:?}", delegation_events).as_str());
-
-    if delegation_events.is_empty() {
-        return Ok(None);
-    }
-    // The following for-loop iterates through successive pairs of
-    // delegation_events, so if there's only one, we need to add a dummy
-    // event so that there is at least one pair.
-    if delegation_events.len() == 1 {
-        let dummy_event = StakingEvent::default();
-        delegation_events.push((seq_no.u64() + 1u64, dummy_event));
+    max_events: Option<u32>,
+) -> Result<TokenSyncResult, ContractError> {
+    let mut result = TokenSyncResult::empty(token);
+
+    if sync_state.seq_no >= seq_no {
+        return Ok(result);
     }
 
+    let delegation_events = load_delegation_events(store, delegator, sync_state, seq_no)?;
+
+    // Determine the weight in effect at the start of the range. If the
+    // account has no delegation events since then, it's unchanged since
+    // then. Otherwise, an earlier event may have been garbage collected, in
+    // which case the prior weight is unknown and nothing accrues until the
+    // next event.
+    let mut weight = match TS_STAKE
+        .prefix(delegator)
+        .range(
+            store,
+            None,
+            Some(Bound::inclusive(sync_state.seq_no.u64())),
+            Order::Descending,
+        )
+        .next()
+    {
+        Some(entry) => entry?.1.delta,
+        None if TS_STAKE
+            .prefix(delegator)
+            .range(
+                store,
+                Some(Bound::inclusive(sync_state.seq_no.u64())),
+                None,
+                Order::Ascending,
+            )
+            .next()
+            .is_none() =>
+        {
+            account.weight()?
+        },
+        None => Uint128::zero(),
+    };
+
     let token_key = token.to_key();
+    let mut delegation_events = delegation_events.into_iter().peekable();
 
-    // Return these keys and delete the events in caller
-    let mut updated_balance_events: Vec<((String, u64), BalanceEvent)> = Vec::with_capacity(2);
-    let mut zombie_balance_event_keys: Vec<(String, u64)> = Vec::with_capacity(2);
-    let mut zombie_delegation_event_keys: Vec<u64> = Vec::with_capacity(2);
+    // Accumulate the delegator's share of revenue for each balance event based
+    // on their weight compared to total weight across all accounts at the time.
+    for entry in TS_BALANCE.range(
+        store,
+        Some(Bound::Inclusive((
+            (&token_key, sync_state.seq_no.u64()),
+            PhantomData,
+        ))),
+        Some(Bound::Exclusive(((&token_key, seq_no.u64()), PhantomData))),
+        Order::Ascending,
+    ) {
+        let (k, mut e) = entry?;
 
-    // Iterate through pairs of delegation events, accumulating the
-    // aggregate sync amount within the range between by each pair.
-    for (i, (s1, e1)) in delegation_events
-        .iter()
-        .take(delegation_events.len() - 1)
-        .enumerate()
-    {
-        let account_deleg = e1.delta;
-        let (s2, _) = &delegation_events[i + 1];
+        if max_events.is_some_and(|n| result.n_legacy_events >= n) {
+            result.legacy_cursor = k.1.into();
+            return Ok(result);
+        }
 
-        api.debug(format!("processing delegation events btw: {:?} - {:?}", s1, s2).as_str());
+        while let Some((_, event)) = delegation_events.next_if(|(s, _)| *s <= k.1) {
+            weight = event.delta;
+        }
 
-        // Accumulate the delegator's share of revenue between the given
-        // delegation events.
-        for result in TS_BALANCE.range(
-            store,
-            Some(Bound::Inclusive(((&token_key, *s1), PhantomData))),
-            Some(Bound::Exclusive(((&token_key, *s2), PhantomData))),
-            Order::Ascending,
-        ) {
-            let (k, mut e) = result?;
+        if !e.total.is_zero() {
+            let account_revenue = mul_ratio_u128(e.delta, weight, e.total)?;
+            result.synced_amount = add_u128(result.synced_amount, account_revenue)?;
+        }
 
-            api.debug(format!("processing balance event delta: {:?}", e.delta).as_str());
+        e.ref_count = e.ref_count.saturating_sub(1);
 
-            zombie_delegation_event_keys.push(*s1);
+        if e.ref_count == 0 {
+            result.zombie_balance_event_keys.push(k);
+        } else {
+            result.updated_balance_events.push((k, e));
+        }
 
-            // Compute the delegator's share of revenue for this
-            // RevenueEvent based on their delegation compared to total
-            // delegation across all accounts at that time, and increment
-            // the running total sync amount.
-            let account_revenue = mul_ratio_u128(e.delta, account_deleg, e.total)?;
-            agg_sync_amount = add_u128(agg_sync_amount, account_revenue)?;
+        result.n_legacy_events += 1;
+    }
 
-            e.ref_count = sub_u32(e.ref_count, 1)?;
+    result.legacy_complete = true;
+    result.legacy_cursor = seq_no;
 
-            if e.ref_count == 0 {
-                zombie_balance_event_keys.push(k);
-            } else {
-                updated_balance_events.push((k, e));
-            }
+    Ok(result)
+}
+
+/// Delete the account's legacy delegation events once every token has been
+/// synced through the end of the legacy event series.
+fn prune_legacy_delegation_events(
+    store: &mut dyn Storage,
+    staker: &Addr,
+    seq_no: Uint64,
+) -> Result<(), ContractError> {
+    let account = if let Some(account) = ACCOUNTS.may_load(store, staker)? {
+        account
+    } else {
+        return Ok(());
+    };
+
+    for token in load_reward_tokens(store)?.iter() {
+        if load_sync_state(store, staker, &account, token)?.seq_no < seq_no {
+            return Ok(());
         }
     }
 
-    Ok(Some(TokenSyncResult {
-        updated_balance_events,
-        zombie_balance_event_keys,
-        zombie_delegation_event_keys,
-        synced_amount: agg_sync_amount,
-        token: token.to_owned(),
-    }))
+    for key in TS_STAKE
+        .prefix(staker)
+        .keys(store, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?
+    {
+        TS_STAKE.remove(store, (staker, key));
+    }
+
+    Ok(())
 }
 
 pub fn load_delegation_events(
@@ -268,10 +798,10 @@ pub fn load_delegation_events(
     for result in TS_STAKE.range(
         store,
         Some(Bound::Inclusive((
-            (&delegator, sync.seq_no.u64()),
+            (delegator, sync.seq_no.u64()),
             PhantomData,
         ))),
-        Some(Bound::Exclusive(((&delegator, seq_no.u64()), PhantomData))),
+        Some(Bound::Exclusive(((delegator, seq_no.u64()), PhantomData))),
         Order::Ascending,
     ) {
         let ((_, seq_no), event) = result?;
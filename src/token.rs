@@ -1,10 +1,13 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
-    to_json_binary, Addr, BankMsg, Coin, CosmosMsg, Empty, QuerierWrapper, StdError, StdResult,
+    to_json_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Empty, QuerierWrapper, StdResult,
     SubMsg, Uint128, WasmMsg,
 };
 use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg};
 
+/// Protobuf type URL of the tokenfactory module's MsgBurn
+pub const TOKENFACTORY_MSG_BURN_TYPE_URL: &str = "/osmosis.tokenfactory.v1beta1.MsgBurn";
+
 #[cw_serde]
 pub enum Token {
     Denom(String),
@@ -25,9 +28,9 @@ impl Token {
         }
     }
 
-    pub fn from_key(key: &String) -> Self {
-        if key.starts_with("a_") {
-            Self::Address(Addr::unchecked(key[2..].to_string()))
+    pub fn from_key(key: &str) -> Self {
+        if let Some(address) = key.strip_prefix("a_") {
+            Self::Address(Addr::unchecked(address))
         } else {
             Self::Denom(key[2..].to_string())
         }
@@ -90,16 +93,75 @@ impl Token {
             }),
         })
     }
+
+    /// Send token amount to a contract along with a message for it to execute.
+    /// CW20 tokens use Cw20ExecuteMsg::Send, which delivers the message in a
+    /// Cw20ReceiveMsg, while denoms are attached as funds to the message itself.
+    pub fn send(
+        &self,
+        contract: &Addr,
+        amount: Uint128,
+        msg: Binary,
+    ) -> StdResult<SubMsg> {
+        Ok(match self {
+            Self::Denom(denom) => SubMsg::new(WasmMsg::Execute {
+                contract_addr: contract.clone().into(),
+                msg,
+                funds: vec![Coin::new(amount.u128(), denom)],
+            }),
+            Self::Address(cw20_addr) => SubMsg::new(WasmMsg::Execute {
+                contract_addr: cw20_addr.clone().into(),
+                msg: to_json_binary(&Cw20ExecuteMsg::Send {
+                    contract: contract.clone().into(),
+                    amount,
+                    msg,
+                })?,
+                funds: vec![],
+            }),
+        })
+    }
+
+    /// Transfer token amount to the recipient, or send it along with a
+    /// message if one is given.
+    pub fn transfer_or_send(
+        &self,
+        recipient: &Addr,
+        amount: Uint128,
+        msg: Option<Binary>,
+    ) -> StdResult<SubMsg> {
+        if let Some(msg) = msg {
+            self.send(recipient, amount, msg)
+        } else {
+            self.transfer(recipient, amount)
+        }
+    }
+
+    /// Whether the contract can burn the token. Tokenfactory denoms can only
+    /// be burned by their admin, which is assumed to be the creator, i.e. the
+    /// contract if the denom is under its namespace. CW20 tokens are burned
+    /// from the contract's own balance.
+    pub fn is_burnable(
+        &self,
+        contract_addr: &Addr,
+    ) -> bool {
+        match self {
+            Self::Denom(denom) => denom.starts_with(&format!("factory/{}/", contract_addr)),
+            Self::Address(_) => true,
+        }
+    }
+
+    /// Burn token amount held by the contract. Denoms are burned through the
+    /// tokenfactory module, which requires the contract to be the denom admin.
     pub fn burn(
         &self,
+        contract_addr: &Addr,
         amount: Uint128,
     ) -> StdResult<SubMsg> {
         Ok(match self {
-            Self::Denom(_denom) => {
-                return Err(StdError::GenericErr {
-                    msg: "tokenfactory burn not implemented".to_owned(),
-                });
-            },
+            Self::Denom(denom) => SubMsg::new(CosmosMsg::Stargate {
+                type_url: TOKENFACTORY_MSG_BURN_TYPE_URL.to_owned(),
+                value: encode_msg_burn(contract_addr, denom, amount),
+            }),
             Self::Address(cw20_addr) => SubMsg::new(WasmMsg::Execute {
                 contract_addr: cw20_addr.clone().into(),
                 msg: to_json_binary(&Cw20ExecuteMsg::Burn { amount })?,
@@ -110,17 +172,14 @@ impl Token {
 
     pub fn has_in_funds(
         &self,
-        funds_to_search: &Vec<Coin>,
+        funds_to_search: &[Coin],
         exact_amount: Option<Uint128>,
     ) -> bool {
         if let Self::Denom(denom) = self {
             funds_to_search
                 .iter()
                 .find(|c| {
-                    c.denom == *denom
-                        && (exact_amount
-                            .and_then(|n| Some(n == c.amount))
-                            .unwrap_or(true))
+                    c.denom == *denom && (exact_amount.map(|n| n == c.amount).unwrap_or(true))
                 })
                 .is_some()
         } else {
@@ -128,3 +187,87 @@ impl Token {
         }
     }
 }
+
+/// Protobuf-encode a tokenfactory MsgBurn { sender, amount: Coin }.
+fn encode_msg_burn(
+    sender: &Addr,
+    denom: &str,
+    amount: Uint128,
+) -> Binary {
+    let mut coin: Vec<u8> = Vec::with_capacity(64);
+    encode_proto_bytes(1, denom.as_bytes(), &mut coin);
+    encode_proto_bytes(2, amount.to_string().as_bytes(), &mut coin);
+
+    let mut msg: Vec<u8> = Vec::with_capacity(128);
+    encode_proto_bytes(1, sender.as_bytes(), &mut msg);
+    encode_proto_bytes(2, &coin, &mut msg);
+
+    Binary::from(msg)
+}
+
+/// Append a length-delimited protobuf field to the buffer.
+fn encode_proto_bytes(
+    field: u8,
+    bytes: &[u8],
+    buf: &mut Vec<u8>,
+) {
+    buf.push((field << 3) | 2);
+    let mut len = bytes.len();
+    while len >= 0x80 {
+        buf.push((len as u8 & 0x7f) | 0x80);
+        len >>= 7;
+    }
+    buf.push(len as u8);
+    buf.extend_from_slice(bytes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_msg_burn() {
+        let msg = encode_msg_burn(
+            &Addr::unchecked("contract0"),
+            "factory/contract0/urev",
+            Uint128::new(1000),
+        );
+
+        let expected = [
+            &[0x0a, 9][..],
+            b"contract0",
+            &[0x12, 30],
+            &[0x0a, 22],
+            b"factory/contract0/urev",
+            &[0x12, 4],
+            b"1000",
+        ]
+        .concat();
+
+        assert_eq!(msg, Binary::from(expected));
+    }
+
+    #[test]
+    fn encodes_multibyte_field_lengths() {
+        let sender = "a".repeat(200);
+        let msg = encode_msg_burn(&Addr::unchecked(&sender), "factory/a/b", Uint128::new(5));
+
+        // 200 is encoded as the varint 0xc8 0x01
+        assert_eq!(&msg[..3], &[0x0a, 0xc8, 0x01]);
+        assert_eq!(&msg[3..203], sender.as_bytes());
+        assert_eq!(
+            &msg[203..],
+            &[&[0x12, 16, 0x0a, 11][..], b"factory/a/b", &[0x12, 1], b"5"].concat()[..]
+        );
+    }
+
+    #[test]
+    fn only_owned_denoms_are_burnable() {
+        let contract = Addr::unchecked("contract0");
+        assert!(Token::Denom("factory/contract0/urev".to_owned()).is_burnable(&contract));
+        assert!(!Token::Denom("factory/contract01/urev".to_owned()).is_burnable(&contract));
+        assert!(!Token::Denom("factory/other/urev".to_owned()).is_burnable(&contract));
+        assert!(!Token::Denom("ujuno".to_owned()).is_burnable(&contract));
+        assert!(Token::Address(Addr::unchecked("cw20")).is_burnable(&contract));
+    }
+}
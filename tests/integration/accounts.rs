@@ -0,0 +1,91 @@
+use cosmwasm_std::Addr;
+use cw_sath::{
+    msg::{ExecuteMsg, QueryMsg},
+    responses::StakerResponseItem,
+};
+
+use crate::{
+    legacy::{seed_msg, setup_legacy},
+    suite::{instantiate_msg, setup, Suite, ALICE, BOB},
+};
+
+const CAROL: &str = "carol";
+
+fn top_stakers(
+    suite: &Suite,
+    limit: Option<u32>,
+) -> Vec<(String, u128)> {
+    suite
+        .query::<Vec<StakerResponseItem>>(&QueryMsg::TopStakers { limit })
+        .into_iter()
+        .map(|s| (s.address.to_string(), s.delegation.u128()))
+        .collect()
+}
+
+#[test]
+fn top_stakers_are_ordered_by_delegation() {
+    let mut suite = setup(instantiate_msg()).unwrap();
+    suite.stake(ALICE, 2_000).unwrap();
+    suite.stake(BOB, 3_000).unwrap();
+    suite.stake(CAROL, 1_000).unwrap();
+
+    assert_eq!(
+        top_stakers(&suite, Some(2)),
+        vec![(BOB.to_owned(), 3_000), (ALICE.to_owned(), 2_000)]
+    );
+
+    // Unstaking reorders the index
+    suite.unstake(BOB, 2_500).unwrap();
+    assert_eq!(
+        top_stakers(&suite, None),
+        vec![
+            (ALICE.to_owned(), 2_000),
+            (CAROL.to_owned(), 1_000),
+            (BOB.to_owned(), 500)
+        ]
+    );
+}
+
+#[test]
+fn accounts_page_in_address_order() {
+    let mut suite = setup(instantiate_msg()).unwrap();
+    for staker in [CAROL, ALICE, BOB] {
+        suite.stake(staker, 1_000).unwrap();
+    }
+
+    let page = |start_after: Option<&str>| -> Vec<String> {
+        suite
+            .query::<Vec<StakerResponseItem>>(&QueryMsg::Accounts {
+                start_after: start_after.map(Addr::unchecked),
+                limit: Some(2),
+            })
+            .into_iter()
+            .map(|s| s.address.to_string())
+            .collect()
+    };
+
+    assert_eq!(page(None), vec![ALICE, BOB]);
+    assert_eq!(page(Some(BOB)), vec![CAROL]);
+    assert!(page(Some(CAROL)).is_empty());
+}
+
+#[test]
+fn migrated_accounts_are_indexed_as_cranked() {
+    let mut suite = setup_legacy(seed_msg(1));
+
+    // Accounts saved before the index existed aren't listed until cranked
+    assert!(top_stakers(&suite, None).is_empty());
+
+    suite
+        .execute(ALICE, &ExecuteMsg::Crank { limit: Some(1) }, &[])
+        .unwrap();
+    assert_eq!(top_stakers(&suite, None), vec![(ALICE.to_owned(), 1_000)]);
+
+    suite
+        .execute(ALICE, &ExecuteMsg::Crank { limit: Some(1) }, &[])
+        .unwrap();
+    assert_eq!(
+        top_stakers(&suite, None),
+        vec![(BOB.to_owned(), 3_000), (ALICE.to_owned(), 1_000)]
+    );
+}
@@ -0,0 +1,95 @@
+use cosmwasm_std::{Uint128, Uint64};
+use cw_sath::{
+    msg::{AdminMsg, ExecuteMsg, HouseMarketingInfo, QueryMsg},
+    responses::{ConfigResponse, HouseResponse},
+    token::Token,
+};
+
+use crate::suite::{assert_err, instantiate_msg, setup, ALICE};
+
+#[test]
+fn manager_updates_house_config() {
+    let mut suite = setup(instantiate_msg()).unwrap();
+
+    suite
+        .admin(AdminMsg::UpdateMarketingInfo(HouseMarketingInfo {
+            name: Some("casino".to_owned()),
+            description: Some("house of games".to_owned()),
+            logo: None,
+        }))
+        .unwrap();
+    suite
+        .admin(AdminMsg::UpdateUnbondingSeconds {
+            seconds: Uint64::new(3_600),
+        })
+        .unwrap();
+    suite
+        .admin(AdminMsg::SetMinIncrement {
+            amount: Uint128::new(10),
+        })
+        .unwrap();
+    suite
+        .admin(AdminMsg::AddRevenueToken {
+            token: Token::Denom("urev".to_owned()),
+        })
+        .unwrap();
+
+    let config = suite.query::<ConfigResponse>(&QueryMsg::Config {});
+    assert_eq!(config.unbonding_seconds, Uint64::new(3_600));
+    assert_eq!(config.min_increment, Uint128::new(10));
+    assert!(config
+        .revenue_tokens
+        .contains(&Token::Denom("urev".to_owned())));
+
+    let house = suite.query::<HouseResponse>(&QueryMsg::House {});
+    assert_eq!(house.marketing.name.as_deref(), Some("casino"));
+
+    suite
+        .admin(AdminMsg::RemoveRevenueToken {
+            token: Token::Denom("urev".to_owned()),
+        })
+        .unwrap();
+    let config = suite.query::<ConfigResponse>(&QueryMsg::Config {});
+    assert!(!config
+        .revenue_tokens
+        .contains(&Token::Denom("urev".to_owned())));
+}
+
+#[test]
+fn rejects_admin_messages_from_others_and_invalid_config() {
+    let mut suite = setup(instantiate_msg()).unwrap();
+
+    assert_err(
+        suite.execute(
+            ALICE,
+            &ExecuteMsg::Admin(AdminMsg::SetMinIncrement {
+                amount: Uint128::new(10),
+            }),
+            &[],
+        ),
+        "only the contract manager can perform this action",
+    );
+    assert_err(
+        suite.admin(AdminMsg::UpdateUnbondingSeconds {
+            seconds: Uint64::new(60 * 60 * 24 * 366),
+        }),
+        "unbonding seconds cannot exceed",
+    );
+    assert_err(
+        suite.admin(AdminMsg::AddRevenueToken {
+            token: Token::Denom("ustake".to_owned()),
+        }),
+        "revenue token already exists",
+    );
+    assert_err(
+        suite.admin(AdminMsg::UpdateMarketingInfo(HouseMarketingInfo {
+            name: Some("x".repeat(65)),
+            description: None,
+            logo: None,
+        })),
+        "name",
+    );
+
+    let config = suite.query::<ConfigResponse>(&QueryMsg::Config {});
+    assert_eq!(config.min_increment, Uint128::zero());
+}
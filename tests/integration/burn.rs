@@ -0,0 +1,45 @@
+use cosmwasm_std::Uint128;
+use cw_sath::{msg::AdminMsg, token::Token};
+
+use crate::suite::{assert_err, instantiate_msg, setup, ALICE, HOUSE_ADDR};
+
+/// Tokenfactory denom created by the house
+const OWNED_DENOM: &str = "factory/contract0/urev";
+
+#[test]
+fn burns_share_of_deposits_via_msg_burn() {
+    let mut msg = instantiate_msg();
+    msg.staking.revenue_tokens = vec![Token::Denom(OWNED_DENOM.to_owned())];
+    msg.burn_pct = Some(Uint128::new(100_000));
+    let mut suite = setup(msg).unwrap();
+
+    suite.stake(ALICE, 1_000).unwrap();
+    suite.deposit(ALICE, 1_000, OWNED_DENOM).unwrap();
+
+    assert_eq!(suite.balance(HOUSE_ADDR, OWNED_DENOM), 900);
+    assert_eq!(suite.pending(ALICE, OWNED_DENOM), 900);
+}
+
+#[test]
+fn rejects_burn_pct_with_unburnable_revenue_token() {
+    let mut msg = instantiate_msg();
+    msg.burn_pct = Some(Uint128::new(100_000));
+    assert_err(setup(msg), "cannot be burned by the contract");
+
+    let mut msg = instantiate_msg();
+    msg.staking.revenue_tokens = vec![Token::Denom(OWNED_DENOM.to_owned())];
+    msg.burn_pct = Some(Uint128::new(100_000));
+    let mut suite = setup(msg).unwrap();
+
+    assert_err(
+        suite.admin(AdminMsg::AddRevenueToken {
+            token: Token::Denom("factory/other/urev".to_owned()),
+        }),
+        "cannot be burned by the contract",
+    );
+    suite
+        .admin(AdminMsg::AddRevenueToken {
+            token: Token::Denom("factory/contract0/uother".to_owned()),
+        })
+        .unwrap();
+}
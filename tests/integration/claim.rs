@@ -0,0 +1,279 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response,
+    StdError, StdResult, Uint128, Uint64,
+};
+use cw20::Cw20ReceiveMsg;
+use cw_multi_test::{error::AnyResult, AppResponse, ContractWrapper, Executor};
+use cw_sath::{
+    msg::{ClaimMsg, ExecuteMsg, InstantiateMsg, ReceiveHookMsg},
+    token::Token,
+};
+use cw_storage_plus::Item;
+
+use crate::{
+    cw20::{cw20_balance, send, setup_cw20, STAKE_HOOK},
+    suite::{assert_err, instantiate_msg, setup, Suite, ALICE, BOB, MANAGER, STAKE_DENOM},
+};
+
+const REV_DENOM: &str = "urev";
+const OTHER_DENOM: &str = "uother";
+
+/// Set up a house earning three revenue tokens, with Alice staked
+fn setup_claims() -> Suite {
+    let mut msg: InstantiateMsg = instantiate_msg();
+    msg.staking.unbonding_seconds = Some(Uint64::new(100));
+    for denom in [REV_DENOM, OTHER_DENOM] {
+        msg.staking
+            .revenue_tokens
+            .push(Token::Denom(denom.to_owned()));
+    }
+    let mut suite = setup(msg).unwrap();
+    suite.stake(ALICE, 1_000).unwrap();
+    suite
+}
+
+const RECEIPT: Item<String> = Item::new("receipt");
+
+/// Message executed by a vault upon receipt of claimed tokens
+#[cw_serde]
+enum VaultMsg {
+    Deposit { memo: String },
+    Receive(Cw20ReceiveMsg),
+}
+
+/// Vault that records the memo and amount of the tokens it receives, and
+/// rejects the memo "reject"
+fn vault_execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: VaultMsg,
+) -> StdResult<Response> {
+    let (memo, amount) = match msg {
+        VaultMsg::Deposit { memo } => (memo, info.funds[0].amount),
+        VaultMsg::Receive(receive) => match from_json(&receive.msg)? {
+            VaultMsg::Deposit { memo } => (memo, receive.amount),
+            VaultMsg::Receive(_) => return Err(StdError::generic_err("nested receive")),
+        },
+    };
+    if memo == "reject" {
+        return Err(StdError::generic_err("vault rejected the deposit"));
+    }
+    RECEIPT.save(deps.storage, &format!("{} {}", memo, amount))?;
+    Ok(Response::new())
+}
+
+fn vault_query(
+    deps: Deps,
+    _env: Env,
+    _msg: Empty,
+) -> StdResult<Binary> {
+    to_json_binary(&RECEIPT.load(deps.storage)?)
+}
+
+fn setup_vault(suite: &mut Suite) -> Addr {
+    let code_id = suite.app.store_code(Box::new(ContractWrapper::new(
+        vault_execute,
+        |_: DepsMut, _: Env, _: MessageInfo, _: Empty| -> StdResult<Response> {
+            Ok(Response::new())
+        },
+        vault_query,
+    )));
+    suite
+        .app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(MANAGER),
+            &Empty {},
+            &[],
+            "vault",
+            None,
+        )
+        .unwrap()
+}
+
+fn receipt(
+    suite: &Suite,
+    vault: &Addr,
+) -> String {
+    suite.app.wrap().query_wasm_smart(vault, &Empty {}).unwrap()
+}
+
+fn claim_to(
+    suite: &mut Suite,
+    token: Token,
+    recipient: &Addr,
+    memo: Option<&str>,
+) -> AnyResult<AppResponse> {
+    suite.execute(
+        ALICE,
+        &ExecuteMsg::Claim(ClaimMsg {
+            token: Some(token),
+            recipient: Some(recipient.to_owned()),
+            msg: memo.map(|memo| {
+                to_json_binary(&VaultMsg::Deposit {
+                    memo: memo.to_owned(),
+                })
+                .unwrap()
+            }),
+        }),
+        &[],
+    )
+}
+
+#[test]
+fn claims_every_token_and_matured_unbonding_at_once() {
+    let mut suite = setup_claims();
+
+    suite.unstake(ALICE, 500).unwrap();
+    suite.deposit(MANAGER, 100, STAKE_DENOM).unwrap();
+    suite.deposit(MANAGER, 200, REV_DENOM).unwrap();
+    suite.deposit(MANAGER, 300, OTHER_DENOM).unwrap();
+    suite
+        .app
+        .update_block(|block| block.time = block.time.plus_seconds(100));
+
+    let res = suite.claim(ALICE).unwrap();
+    let wasm = res.events.iter().find(|e| e.ty == "wasm").unwrap();
+    assert_eq!(
+        wasm.attributes.iter().filter(|a| a.key == "token").count(),
+        3
+    );
+
+    assert_eq!(suite.balance(ALICE, STAKE_DENOM), 100 + 500);
+    assert_eq!(suite.balance(ALICE, REV_DENOM), 200);
+    assert_eq!(suite.balance(ALICE, OTHER_DENOM), 300);
+
+    let account = suite.account(ALICE).unwrap();
+    assert!(account.balances.iter().all(|b| b.amount.is_zero()));
+    assert!(account.unbondings.is_empty());
+    assert_eq!(account.delegation, Uint128::new(500));
+}
+
+#[test]
+fn claims_only_the_given_token() {
+    let mut suite = setup_claims();
+
+    suite.deposit(MANAGER, 200, REV_DENOM).unwrap();
+    suite.deposit(MANAGER, 300, OTHER_DENOM).unwrap();
+
+    suite
+        .execute(
+            ALICE,
+            &ExecuteMsg::Claim(ClaimMsg {
+                token: Some(Token::Denom(REV_DENOM.to_owned())),
+                recipient: None,
+                msg: None,
+            }),
+            &[],
+        )
+        .unwrap();
+
+    assert_eq!(suite.balance(ALICE, REV_DENOM), 200);
+    assert_eq!(suite.balance(ALICE, OTHER_DENOM), 0);
+    assert_eq!(suite.pending(ALICE, OTHER_DENOM), 300);
+
+    // Claiming again pays out only what's left
+    suite.claim(ALICE).unwrap();
+    assert_eq!(suite.balance(ALICE, REV_DENOM), 200);
+    assert_eq!(suite.balance(ALICE, OTHER_DENOM), 300);
+}
+
+#[test]
+fn rejects_claims_without_an_account() {
+    let mut suite = setup_claims();
+    suite.deposit(MANAGER, 200, REV_DENOM).unwrap();
+
+    assert_err(suite.claim(BOB), "not found");
+    assert_eq!(suite.pending(ALICE, REV_DENOM), 200);
+
+    // A claim with nothing pending transfers nothing
+    suite.claim(ALICE).unwrap();
+    let res = suite.claim(ALICE).unwrap();
+    assert!(res.events.iter().all(|e| e.ty != "transfer"));
+}
+
+#[test]
+fn claims_to_a_recipient_with_a_callback() {
+    let mut suite = setup_claims();
+    let vault = setup_vault(&mut suite);
+    let rev = Token::Denom(REV_DENOM.to_owned());
+
+    suite.deposit(MANAGER, 200, REV_DENOM).unwrap();
+    suite.deposit(MANAGER, 300, OTHER_DENOM).unwrap();
+
+    claim_to(&mut suite, rev, &vault, Some("rewards")).unwrap();
+    assert_eq!(receipt(&suite, &vault), "rewards 200");
+    assert_eq!(suite.balance(vault.as_str(), REV_DENOM), 200);
+
+    // Without a msg, tokens are transferred to the recipient
+    claim_to(
+        &mut suite,
+        Token::Denom(OTHER_DENOM.to_owned()),
+        &Addr::unchecked(BOB),
+        None,
+    )
+    .unwrap();
+    assert_eq!(suite.balance(BOB, OTHER_DENOM), 300);
+    assert_eq!(suite.balance(ALICE, OTHER_DENOM), 0);
+}
+
+#[test]
+fn claims_cw20_with_a_callback() {
+    let (mut suite, token) = setup_cw20();
+    let vault = setup_vault(&mut suite);
+
+    send(&mut suite, &token, ALICE, 1_000, &STAKE_HOOK).unwrap();
+    send(
+        &mut suite,
+        &token,
+        MANAGER,
+        400,
+        &ReceiveHookMsg::Deposit {},
+    )
+    .unwrap();
+
+    claim_to(
+        &mut suite,
+        Token::Address(token.to_owned()),
+        &vault,
+        Some("cw20 rewards"),
+    )
+    .unwrap();
+    assert_eq!(receipt(&suite, &vault), "cw20 rewards 400");
+    assert_eq!(cw20_balance(&suite, &token, vault.as_str()), 400);
+}
+
+#[test]
+fn rejects_callbacks_without_a_token_and_reverts_failed_callbacks() {
+    let mut suite = setup_claims();
+    let vault = setup_vault(&mut suite);
+    suite.deposit(MANAGER, 200, REV_DENOM).unwrap();
+
+    assert_err(
+        suite.execute(
+            ALICE,
+            &ExecuteMsg::Claim(ClaimMsg {
+                token: None,
+                recipient: Some(vault.to_owned()),
+                msg: Some(Binary::default()),
+            }),
+            &[],
+        ),
+        "a token must be given to claim with a msg",
+    );
+
+    // The claim is reverted along with the rejected callback
+    assert_err(
+        claim_to(
+            &mut suite,
+            Token::Denom(REV_DENOM.to_owned()),
+            &vault,
+            Some("reject"),
+        ),
+        "vault rejected the deposit",
+    );
+    assert_eq!(suite.pending(ALICE, REV_DENOM), 200);
+    assert_eq!(suite.balance(vault.as_str(), REV_DENOM), 0);
+}
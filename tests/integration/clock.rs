@@ -0,0 +1,66 @@
+use cw_multi_test::AppResponse;
+use cw_sath::msg::SudoMsg;
+
+use crate::{
+    legacy::{n_pending_events, seed_msg, setup_legacy, LEGACY_DENOM},
+    suite::{attribute, instantiate_msg, setup, Suite, ALICE, BOB, MANAGER},
+};
+
+fn end_block(suite: &mut Suite) -> AppResponse {
+    suite.app.update_block(|block| block.height += 1);
+    let house = suite.house.to_owned();
+    suite
+        .app
+        .wasm_sudo(house, &SudoMsg::ClockEndBlock {})
+        .unwrap()
+}
+
+#[test]
+fn clock_syncs_and_prunes_legacy_events_in_small_batches() {
+    let mut suite = setup_legacy(seed_msg(20));
+
+    let mut n_blocks = 0;
+    loop {
+        let resp = end_block(&mut suite);
+        if attribute(&resp, "n_synced").is_none() {
+            break;
+        }
+        assert!(
+            attribute(&resp, "n_synced")
+                .unwrap()
+                .parse::<u32>()
+                .unwrap()
+                <= 1
+        );
+        assert!(
+            attribute(&resp, "n_pruned")
+                .unwrap()
+                .parse::<u32>()
+                .unwrap()
+                <= 10
+        );
+        n_blocks += 1;
+        assert!(n_blocks < 100, "legacy events were never pruned");
+    }
+
+    // Each account synced 5 of its 20 events per block it was cranked
+    assert!(n_blocks >= 8);
+    assert_eq!(n_pending_events(&suite), 0);
+
+    suite.deposit(MANAGER, 400, LEGACY_DENOM).unwrap();
+    assert_eq!(suite.pending(ALICE, LEGACY_DENOM), 20 + 100);
+    assert_eq!(suite.pending(BOB, LEGACY_DENOM), 60 + 300);
+}
+
+#[test]
+fn clock_does_nothing_without_legacy_events() {
+    let mut suite = setup(instantiate_msg()).unwrap();
+    suite.stake(ALICE, 1_000).unwrap();
+
+    let resp = end_block(&mut suite);
+    assert_eq!(
+        attribute(&resp, "action").as_deref(),
+        Some("clock_end_block")
+    );
+    assert_eq!(attribute(&resp, "n_synced"), None);
+}
@@ -0,0 +1,102 @@
+use cosmwasm_std::Uint64;
+use cw_sath::{
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    responses::CrankResponse,
+};
+
+use crate::suite::{
+    assert_err, attribute, instantiate_msg, setup, Suite, ALICE, BOB, MANAGER, STAKE_DENOM,
+};
+
+/// Set up a house with Alice and Bob staked equally and 100 seconds of
+/// unbonding
+fn setup_close() -> Suite {
+    let mut msg: InstantiateMsg = instantiate_msg();
+    msg.staking.unbonding_seconds = Some(Uint64::new(100));
+    let mut suite = setup(msg).unwrap();
+    suite.stake(ALICE, 1_000).unwrap();
+    suite.stake(BOB, 1_000).unwrap();
+    suite
+}
+
+fn crank_info(suite: &Suite) -> CrankResponse {
+    suite.query(&QueryMsg::Crank {})
+}
+
+fn wait_for_unbonding(suite: &mut Suite) {
+    suite.app.update_block(|block| {
+        block.time = block.time.plus_seconds(100);
+        block.height += 1;
+    });
+}
+
+#[test]
+fn closes_exited_accounts_which_can_stake_again() {
+    let mut suite = setup_close();
+    suite.deposit(MANAGER, 2_000, STAKE_DENOM).unwrap();
+
+    // Unbonding and pending revenue keep the account open
+    let resp = suite.unstake(ALICE, 1_000).unwrap();
+    assert_eq!(attribute(&resp, "closed"), None);
+
+    wait_for_unbonding(&mut suite);
+    let resp = suite.claim(ALICE).unwrap();
+    assert_eq!(attribute(&resp, "closed").as_deref(), Some("true"));
+    assert_eq!(suite.balance(ALICE, STAKE_DENOM), 1_000 + 1_000);
+    assert!(suite.account(ALICE).is_none());
+    assert_eq!(crank_info(&suite).n_accounts, 1);
+
+    // Re-staking opens a fresh account that earns only from now on, without
+    // queueing the address twice
+    suite.stake(ALICE, 500).unwrap();
+    assert_eq!(crank_info(&suite).n_accounts, 2);
+    assert_eq!(suite.pending(ALICE, STAKE_DENOM), 0);
+
+    suite.deposit(MANAGER, 1_500, STAKE_DENOM).unwrap();
+    assert_eq!(suite.pending(ALICE, STAKE_DENOM), 500);
+    assert_eq!(suite.pending(BOB, STAKE_DENOM), 1_000 + 1_000);
+
+    suite
+        .execute(BOB, &ExecuteMsg::Crank { limit: Some(10) }, &[])
+        .unwrap();
+    assert_eq!(crank_info(&suite).queue_size, 2);
+}
+
+#[test]
+fn drops_closed_accounts_from_the_queue() {
+    let mut suite = setup_close();
+
+    suite.unstake(ALICE, 1_000).unwrap();
+    wait_for_unbonding(&mut suite);
+    suite.claim(ALICE).unwrap();
+
+    suite
+        .execute(BOB, &ExecuteMsg::Crank { limit: Some(10) }, &[])
+        .unwrap();
+    let info = crank_info(&suite);
+    assert_eq!(info.queue_size, 1);
+    assert_eq!(info.n_accounts, 1);
+
+    suite.stake(ALICE, 500).unwrap();
+    let info = crank_info(&suite);
+    assert_eq!(info.queue_size, 2);
+    assert_eq!(info.n_accounts, 2);
+}
+
+#[test]
+fn keeps_accounts_open_until_unbonding_is_claimed() {
+    let mut suite = setup_close();
+
+    suite.unstake(ALICE, 1_000).unwrap();
+    let resp = suite.claim(ALICE).unwrap();
+    assert_eq!(attribute(&resp, "closed"), None);
+    assert_eq!(suite.account(ALICE).unwrap().unbondings.len(), 1);
+    assert_eq!(crank_info(&suite).n_accounts, 2);
+
+    wait_for_unbonding(&mut suite);
+    suite.claim(ALICE).unwrap();
+
+    // Closed accounts no longer exist to unstake or claim from
+    assert_err(suite.unstake(ALICE, 1), "Account not found");
+    assert_err(suite.claim(ALICE), "not found");
+}
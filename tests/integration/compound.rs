@@ -0,0 +1,109 @@
+use cosmwasm_std::Uint128;
+use cw_multi_test::{error::AnyResult, AppResponse};
+use cw_sath::{
+    msg::{ExecuteMsg, InstantiateMsg, QueryMsg},
+    responses::HouseResponse,
+    token::Token,
+};
+
+use crate::suite::{assert_err, instantiate_msg, setup, Suite, ALICE, BOB, MANAGER, STAKE_DENOM};
+
+const REV_DENOM: &str = "urev";
+
+/// Set up a house earning revenue in the staking token and another token,
+/// with Alice and Bob staked equally
+fn setup_compound() -> Suite {
+    let mut msg: InstantiateMsg = instantiate_msg();
+    msg.staking
+        .revenue_tokens
+        .push(Token::Denom(REV_DENOM.to_owned()));
+    let mut suite = setup(msg).unwrap();
+    suite.stake(ALICE, 1_000).unwrap();
+    suite.stake(BOB, 1_000).unwrap();
+    suite
+}
+
+fn compound(
+    suite: &mut Suite,
+    sender: &str,
+) -> AnyResult<AppResponse> {
+    suite.execute(sender, &ExecuteMsg::Compound {}, &[])
+}
+
+fn set_auto_compound(
+    suite: &mut Suite,
+    sender: &str,
+    enabled: bool,
+) -> AnyResult<AppResponse> {
+    suite.execute(sender, &ExecuteMsg::SetAutoCompound { enabled }, &[])
+}
+
+#[test]
+fn compounded_revenue_earns_like_stake() {
+    let mut suite = setup_compound();
+
+    suite.deposit(MANAGER, 2_000, STAKE_DENOM).unwrap();
+    compound(&mut suite, ALICE).unwrap();
+
+    let account = suite.account(ALICE).unwrap();
+    assert_eq!(account.delegation, Uint128::new(2_000));
+    assert_eq!(suite.pending(ALICE, STAKE_DENOM), 0);
+
+    suite.deposit(MANAGER, 3_000, STAKE_DENOM).unwrap();
+    assert_eq!(suite.pending(ALICE, STAKE_DENOM), 2_000);
+    assert_eq!(suite.pending(BOB, STAKE_DENOM), 1_000 + 1_000);
+
+    let house = suite.query::<HouseResponse>(&QueryMsg::House {});
+    assert_eq!(house.delegation.amount, Uint128::new(3_000));
+}
+
+#[test]
+fn auto_compounds_staking_revenue_on_claim() {
+    let mut suite = setup_compound();
+    set_auto_compound(&mut suite, ALICE, true).unwrap();
+    assert!(suite.account(ALICE).unwrap().auto_compound);
+
+    suite.deposit(MANAGER, 2_000, STAKE_DENOM).unwrap();
+    suite.deposit(MANAGER, 600, REV_DENOM).unwrap();
+    suite.claim(ALICE).unwrap();
+
+    // Other revenue is still paid out
+    assert_eq!(suite.balance(ALICE, STAKE_DENOM), 0);
+    assert_eq!(suite.balance(ALICE, REV_DENOM), 300);
+    assert_eq!(
+        suite.account(ALICE).unwrap().delegation,
+        Uint128::new(2_000)
+    );
+
+    // Once disabled, claims pay out staking revenue again
+    set_auto_compound(&mut suite, ALICE, false).unwrap();
+    suite.deposit(MANAGER, 3_000, STAKE_DENOM).unwrap();
+    suite.claim(ALICE).unwrap();
+    assert_eq!(suite.balance(ALICE, STAKE_DENOM), 2_000);
+    assert_eq!(
+        suite.account(ALICE).unwrap().delegation,
+        Uint128::new(2_000)
+    );
+}
+
+#[test]
+fn rejects_compounding_without_staking_revenue_or_an_account() {
+    let mut suite = setup_compound();
+
+    suite.deposit(MANAGER, 600, REV_DENOM).unwrap();
+    assert_err(
+        compound(&mut suite, ALICE),
+        "no staking token revenue to compound",
+    );
+    assert_eq!(suite.pending(ALICE, REV_DENOM), 300);
+    assert_eq!(
+        suite.account(ALICE).unwrap().delegation,
+        Uint128::new(1_000)
+    );
+
+    assert_err(compound(&mut suite, MANAGER), "Account not found");
+    assert_err(
+        set_auto_compound(&mut suite, MANAGER, true),
+        "Account not found",
+    );
+}
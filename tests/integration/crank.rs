@@ -0,0 +1,47 @@
+use cw_sath::{
+    msg::{ExecuteMsg, QueryMsg},
+    responses::CrankResponse,
+};
+
+use crate::suite::{instantiate_msg, setup, Suite, ALICE, BOB};
+
+fn crank(
+    suite: &mut Suite,
+    limit: u32,
+) {
+    suite
+        .execute(ALICE, &ExecuteMsg::Crank { limit: Some(limit) }, &[])
+        .unwrap();
+}
+
+#[test]
+fn reports_the_last_crank() {
+    let mut suite = setup(instantiate_msg()).unwrap();
+    suite.stake(ALICE, 1_000).unwrap();
+    suite.stake(BOB, 1_000).unwrap();
+
+    suite.app.update_block(|block| {
+        block.height += 10;
+        block.time = block.time.plus_seconds(60);
+    });
+    crank(&mut suite, 2);
+
+    let block = suite.app.block_info();
+    let last_crank = suite
+        .query::<CrankResponse>(&QueryMsg::Crank {})
+        .last_crank
+        .unwrap();
+    assert_eq!(last_crank.height, block.height);
+    assert_eq!(last_crank.time, block.time);
+    assert_eq!(last_crank.n_synced, 2);
+}
+
+#[test]
+fn cranks_that_sync_nothing_are_not_reported() {
+    let mut suite = setup(instantiate_msg()).unwrap();
+
+    crank(&mut suite, 2);
+
+    let resp = suite.query::<CrankResponse>(&QueryMsg::Crank {});
+    assert_eq!(resp.last_crank, None);
+}
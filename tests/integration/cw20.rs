@@ -0,0 +1,254 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+    Uint128,
+};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg};
+use cw_multi_test::{error::AnyResult, AppResponse, ContractWrapper, Executor};
+use cw_sath::{
+    msg::{ClaimMsg, DepositMsg, ExecuteMsg, ReceiveHookMsg, StakeMsg},
+    token::Token,
+};
+use cw_storage_plus::Map;
+
+use crate::suite::{assert_err, instantiate_msg, new_app, setup_in, Suite, ALICE, BOB, MANAGER};
+
+const BALANCES: Map<&Addr, Uint128> = Map::new("balances");
+
+/// Initial balances of a minimal CW20 token
+#[cw_serde]
+pub struct Cw20InstantiateMsg {
+    pub balances: Vec<(Addr, Uint128)>,
+}
+
+fn cw20_instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: Cw20InstantiateMsg,
+) -> StdResult<Response> {
+    for (addr, amount) in msg.balances.iter() {
+        BALANCES.save(deps.storage, addr, amount)?;
+    }
+    Ok(Response::new())
+}
+
+fn move_balance(
+    deps: &mut DepsMut,
+    from: &Addr,
+    to: Option<&Addr>,
+    amount: Uint128,
+) -> StdResult<()> {
+    BALANCES.update(deps.storage, from, |b| -> StdResult<_> {
+        Ok(b.unwrap_or_default().checked_sub(amount)?)
+    })?;
+    if let Some(to) = to {
+        BALANCES.update(deps.storage, to, |b| -> StdResult<_> {
+            Ok(b.unwrap_or_default() + amount)
+        })?;
+    }
+    Ok(())
+}
+
+fn cw20_execute(
+    mut deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: Cw20ExecuteMsg,
+) -> StdResult<Response> {
+    match msg {
+        Cw20ExecuteMsg::Transfer { recipient, amount } => {
+            let recipient = deps.api.addr_validate(&recipient)?;
+            move_balance(&mut deps, &info.sender, Some(&recipient), amount)?;
+            Ok(Response::new())
+        },
+        Cw20ExecuteMsg::Send {
+            contract,
+            amount,
+            msg,
+        } => {
+            let contract = deps.api.addr_validate(&contract)?;
+            move_balance(&mut deps, &info.sender, Some(&contract), amount)?;
+            Ok(Response::new().add_message(
+                Cw20ReceiveMsg {
+                    sender: info.sender.to_string(),
+                    amount,
+                    msg,
+                }
+                .into_cosmos_msg(contract)?,
+            ))
+        },
+        Cw20ExecuteMsg::Burn { amount } => {
+            move_balance(&mut deps, &info.sender, None, amount)?;
+            Ok(Response::new())
+        },
+        _ => Err(StdError::generic_err("unsupported cw20 message")),
+    }
+}
+
+fn cw20_query(
+    deps: Deps,
+    _env: Env,
+    msg: Cw20QueryMsg,
+) -> StdResult<Binary> {
+    match msg {
+        Cw20QueryMsg::Balance { address } => to_json_binary(&BalanceResponse {
+            balance: BALANCES
+                .may_load(deps.storage, &Addr::unchecked(address))?
+                .unwrap_or_default(),
+        }),
+        _ => Err(StdError::generic_err("unsupported cw20 query")),
+    }
+}
+
+/// Set up a house that stakes and accepts revenue in a CW20 token, of which
+/// Alice, Bob and the manager each hold 10000. Returns the token's address.
+pub fn setup_cw20() -> (Suite, Addr) {
+    let mut app = new_app();
+    let code_id = app.store_code(Box::new(ContractWrapper::new(
+        cw20_execute,
+        cw20_instantiate,
+        cw20_query,
+    )));
+    let balances = [ALICE, BOB, MANAGER]
+        .iter()
+        .map(|a| (Addr::unchecked(*a), Uint128::new(10_000)))
+        .collect();
+    let token = app
+        .instantiate_contract(
+            code_id,
+            Addr::unchecked(MANAGER),
+            &Cw20InstantiateMsg { balances },
+            &[],
+            "token",
+            None,
+        )
+        .unwrap();
+
+    let mut msg = instantiate_msg();
+    msg.staking.staking_token = Token::Address(token.to_owned());
+    msg.staking.revenue_tokens = vec![Token::Address(token.to_owned())];
+
+    (setup_in(app, msg).unwrap(), token)
+}
+
+/// Send CW20 tokens to the house with the given hook
+pub fn send(
+    suite: &mut Suite,
+    token: &Addr,
+    sender: &str,
+    amount: u128,
+    hook: &ReceiveHookMsg,
+) -> AnyResult<AppResponse> {
+    let house = suite.house.to_string();
+    suite.app.execute_contract(
+        Addr::unchecked(sender),
+        token.to_owned(),
+        &Cw20ExecuteMsg::Send {
+            contract: house,
+            amount: Uint128::new(amount),
+            msg: to_json_binary(hook).unwrap(),
+        },
+        &[],
+    )
+}
+
+pub fn cw20_balance(
+    suite: &Suite,
+    token: &Addr,
+    address: &str,
+) -> u128 {
+    suite
+        .app
+        .wrap()
+        .query_wasm_smart::<BalanceResponse>(
+            token,
+            &Cw20QueryMsg::Balance {
+                address: address.to_owned(),
+            },
+        )
+        .unwrap()
+        .balance
+        .u128()
+}
+
+pub const STAKE_HOOK: ReceiveHookMsg = ReceiveHookMsg::Stake {
+    address: None,
+    lock_seconds: None,
+};
+
+#[test]
+fn stakes_and_deposits_cw20_via_send() {
+    let (mut suite, token) = setup_cw20();
+
+    send(&mut suite, &token, ALICE, 1_000, &STAKE_HOOK).unwrap();
+    send(
+        &mut suite,
+        &token,
+        MANAGER,
+        400,
+        &ReceiveHookMsg::Deposit {},
+    )
+    .unwrap();
+
+    let account = suite.account(ALICE).unwrap();
+    assert_eq!(account.delegation, Uint128::new(1_000));
+    assert_eq!(account.balances[0].amount, Uint128::new(400));
+
+    suite
+        .execute(
+            ALICE,
+            &ExecuteMsg::Claim(ClaimMsg {
+                token: None,
+                recipient: None,
+                msg: None,
+            }),
+            &[],
+        )
+        .unwrap();
+    assert_eq!(cw20_balance(&suite, &token, ALICE), 10_000 - 1_000 + 400);
+}
+
+#[test]
+fn rejects_cw20_without_send_or_from_other_tokens() {
+    let (mut suite, token) = setup_cw20();
+
+    assert_err(
+        suite.execute(
+            ALICE,
+            &ExecuteMsg::Stake(StakeMsg {
+                amount: Uint128::new(1_000),
+                address: None,
+                lock_seconds: None,
+            }),
+            &[],
+        ),
+        "CW20 staking token must be staked via Receive",
+    );
+    assert_err(
+        suite.execute(
+            ALICE,
+            &ExecuteMsg::Deposit(DepositMsg {
+                amount: Uint128::new(1_000),
+                token: Token::Address(token.to_owned()),
+            }),
+            &[],
+        ),
+        "CW20 tokens must be deposited via Receive",
+    );
+
+    // A hook sent by any other contract isn't backed by the staking token
+    assert_err(
+        suite.execute(
+            ALICE,
+            &ExecuteMsg::Receive(Cw20ReceiveMsg {
+                sender: ALICE.to_owned(),
+                amount: Uint128::new(1_000),
+                msg: to_json_binary(&STAKE_HOOK).unwrap(),
+            }),
+            &[],
+        ),
+        "token is not the staking token",
+    );
+    assert!(suite.account(ALICE).is_none());
+}
@@ -0,0 +1,249 @@
+use cosmwasm_std::{Addr, Uint128, Uint64};
+use cw_sath::{
+    msg::{AdminMsg, DepositorCategory, DepositorInfo, ExecuteMsg, InstantiateMsg, QueryMsg},
+    responses::DepositsResponse,
+    state::models::{DepositTokenAmount, Depositor},
+    token::Token,
+};
+
+use crate::suite::{assert_err, instantiate_msg, setup, Suite, ALICE, BOB, STAKE_DENOM};
+
+const REV_DENOM: &str = "urev";
+const DICE: &str = "dice";
+const PARTNER: &str = "partner";
+
+/// Set up a house earning revenue in the staking token and another token,
+/// with Alice staked
+fn setup_deposits() -> Suite {
+    let mut msg: InstantiateMsg = instantiate_msg();
+    msg.staking
+        .revenue_tokens
+        .push(Token::Denom(REV_DENOM.to_owned()));
+    let mut suite = setup(msg).unwrap();
+    suite.stake(ALICE, 1_000).unwrap();
+    suite
+}
+
+fn set_depositor(
+    suite: &mut Suite,
+    address: &str,
+    label: &str,
+    category: DepositorCategory,
+) {
+    suite
+        .admin(AdminMsg::SetDepositor {
+            address: Addr::unchecked(address),
+            info: DepositorInfo {
+                label: label.to_owned(),
+                category,
+            },
+        })
+        .unwrap();
+}
+
+fn deposit_amount(
+    denom: &str,
+    amount: u128,
+    n: u64,
+) -> DepositTokenAmount {
+    DepositTokenAmount {
+        n: Uint64::new(n),
+        amount: Uint128::new(amount),
+        token: Token::Denom(denom.to_owned()),
+    }
+}
+
+fn deposits_page(
+    suite: &Suite,
+    token: Option<&str>,
+    start_after: Option<&str>,
+    limit: Option<u32>,
+) -> Vec<Depositor> {
+    suite
+        .query::<DepositsResponse>(&QueryMsg::Deposits {
+            token: token.map(|denom| Token::Denom(denom.to_owned())),
+            start_after: start_after.map(Addr::unchecked),
+            limit,
+        })
+        .depositors
+}
+
+fn addresses(depositors: &[Depositor]) -> Vec<&str> {
+    depositors.iter().map(|d| d.address.as_str()).collect()
+}
+
+#[test]
+fn totals_allowlisted_deposits_by_category() {
+    let mut suite = setup_deposits();
+    set_depositor(&mut suite, DICE, "dice", DepositorCategory::Game);
+    set_depositor(&mut suite, PARTNER, "partner", DepositorCategory::Partner);
+    suite
+        .admin(AdminMsg::SetDepositorAllowlist { enabled: true })
+        .unwrap();
+
+    suite.deposit(DICE, 300, REV_DENOM).unwrap();
+    suite.deposit(DICE, 200, REV_DENOM).unwrap();
+    suite.deposit(PARTNER, 100, REV_DENOM).unwrap();
+    assert_eq!(suite.pending(ALICE, REV_DENOM), 600);
+
+    let deposits = suite.query::<DepositsResponse>(&QueryMsg::Deposits {
+        token: None,
+        start_after: None,
+        limit: None,
+    });
+    assert_eq!(deposits.totals, vec![deposit_amount(REV_DENOM, 600, 3)]);
+    assert_eq!(deposits.categories.len(), 2);
+    assert_eq!(deposits.categories[0].category, DepositorCategory::Game);
+    assert_eq!(
+        deposits.categories[0].totals,
+        vec![deposit_amount(REV_DENOM, 500, 2)]
+    );
+    assert_eq!(deposits.categories[1].category, DepositorCategory::Partner);
+    assert_eq!(
+        deposits.categories[1].totals,
+        vec![deposit_amount(REV_DENOM, 100, 1)]
+    );
+
+    let dice = suite
+        .query::<Option<Depositor>>(&QueryMsg::Depositor {
+            address: Addr::unchecked(DICE),
+        })
+        .unwrap();
+    assert_eq!(dice.info.unwrap().label, "dice");
+    assert_eq!(dice.totals, vec![deposit_amount(REV_DENOM, 500, 2)]);
+}
+
+#[test]
+fn rejects_depositors_missing_from_the_allowlist() {
+    let mut suite = setup_deposits();
+    set_depositor(&mut suite, DICE, "dice", DepositorCategory::Game);
+
+    // Anyone can deposit until the allowlist is enabled
+    suite.deposit(BOB, 100, REV_DENOM).unwrap();
+    suite
+        .admin(AdminMsg::SetDepositorAllowlist { enabled: true })
+        .unwrap();
+
+    assert_err(
+        suite.deposit(BOB, 100, REV_DENOM),
+        "depositor not allowlisted",
+    );
+    assert_eq!(suite.balance(BOB, REV_DENOM), 100);
+
+    suite
+        .admin(AdminMsg::RemoveDepositor {
+            address: Addr::unchecked(DICE),
+        })
+        .unwrap();
+    assert_err(
+        suite.deposit(DICE, 100, REV_DENOM),
+        "depositor not allowlisted",
+    );
+
+    assert_err(
+        suite.admin(AdminMsg::RemoveDepositor {
+            address: Addr::unchecked(DICE),
+        }),
+        "depositor not found",
+    );
+    assert_err(
+        suite.admin(AdminMsg::SetDepositor {
+            address: Addr::unchecked(DICE),
+            info: DepositorInfo {
+                label: "".to_owned(),
+                category: DepositorCategory::Game,
+            },
+        }),
+        "label must be nonempty",
+    );
+    assert_err(
+        suite.execute(
+            BOB,
+            &ExecuteMsg::Admin(AdminMsg::SetDepositorAllowlist { enabled: false }),
+            &[],
+        ),
+        "only the contract manager can perform this action",
+    );
+
+    // Deposits made before the allowlist have no category
+    let deposits = suite.query::<DepositsResponse>(&QueryMsg::Deposits {
+        token: None,
+        start_after: None,
+        limit: None,
+    });
+    assert!(deposits.categories.is_empty());
+    assert_eq!(deposits.totals, vec![deposit_amount(REV_DENOM, 100, 1)]);
+}
+
+#[test]
+fn pages_depositors_across_tokens() {
+    let mut suite = setup_deposits();
+
+    // Amy and Dan deposit both tokens, Ben and Cal only one each
+    suite.deposit("amy", 10, STAKE_DENOM).unwrap();
+    suite.deposit("amy", 20, REV_DENOM).unwrap();
+    suite.deposit("ben", 30, REV_DENOM).unwrap();
+    suite.deposit("cal", 40, STAKE_DENOM).unwrap();
+    suite.deposit("dan", 50, REV_DENOM).unwrap();
+    suite.deposit("dan", 60, STAKE_DENOM).unwrap();
+    suite.deposit("dan", 70, STAKE_DENOM).unwrap();
+
+    let page = deposits_page(&suite, None, None, Some(2));
+    assert_eq!(addresses(&page), vec!["amy", "ben"]);
+    assert_eq!(
+        page[0].totals,
+        vec![
+            deposit_amount(REV_DENOM, 20, 1),
+            deposit_amount(STAKE_DENOM, 10, 1),
+        ]
+    );
+
+    let page = deposits_page(&suite, None, Some("ben"), Some(2));
+    assert_eq!(addresses(&page), vec!["cal", "dan"]);
+    assert_eq!(
+        page[1].totals,
+        vec![
+            deposit_amount(REV_DENOM, 50, 1),
+            deposit_amount(STAKE_DENOM, 130, 2),
+        ]
+    );
+    assert!(deposits_page(&suite, None, Some("dan"), None).is_empty());
+
+    // Filtering by token pages over only its depositors
+    let page = deposits_page(&suite, Some(STAKE_DENOM), None, None);
+    assert_eq!(addresses(&page), vec!["amy", "cal", "dan"]);
+    assert_eq!(page[2].totals, vec![deposit_amount(STAKE_DENOM, 130, 2)]);
+
+    let dan = suite
+        .query::<Option<Depositor>>(&QueryMsg::Depositor {
+            address: Addr::unchecked("dan"),
+        })
+        .unwrap();
+    assert_eq!(
+        dan.totals,
+        deposits_page(&suite, None, Some("cal"), Some(1))[0].totals
+    );
+}
+
+#[test]
+fn caps_the_page_size_and_omits_unknown_depositors() {
+    let mut suite = setup_deposits();
+
+    for i in 0..101 {
+        suite
+            .deposit(&format!("depositor{:03}", i), 1, REV_DENOM)
+            .unwrap();
+    }
+
+    assert_eq!(deposits_page(&suite, None, None, None).len(), 20);
+    let page = deposits_page(&suite, None, None, Some(1_000));
+    assert_eq!(page.len(), 100);
+    assert_eq!(page[99].address, "depositor099");
+
+    assert_eq!(
+        suite.query::<Option<Depositor>>(&QueryMsg::Depositor {
+            address: Addr::unchecked(BOB),
+        }),
+        None
+    );
+}
@@ -0,0 +1,72 @@
+use cosmwasm_std::{coin, coins, Uint128};
+use cw_sath::{
+    msg::{DepositMsg, ExecuteMsg, StakeMsg},
+    token::Token,
+};
+
+use crate::suite::{assert_err, instantiate_msg, setup, ALICE, MANAGER, STAKE_DENOM};
+
+fn stake_msg(amount: u128) -> ExecuteMsg {
+    ExecuteMsg::Stake(StakeMsg {
+        amount: Uint128::new(amount),
+        address: None,
+        lock_seconds: None,
+    })
+}
+
+#[test]
+fn refunds_overpayment_of_stakes_and_deposits() {
+    let mut suite = setup(instantiate_msg()).unwrap();
+
+    suite.mint(ALICE, 1_500, STAKE_DENOM);
+    suite
+        .execute(ALICE, &stake_msg(1_000), &coins(1_500, STAKE_DENOM))
+        .unwrap();
+    assert_eq!(suite.balance(ALICE, STAKE_DENOM), 500);
+    assert_eq!(suite.account(ALICE).unwrap().delegation.u128(), 1_000);
+
+    suite.mint(MANAGER, 500, STAKE_DENOM);
+    suite
+        .execute(
+            MANAGER,
+            &ExecuteMsg::Deposit(DepositMsg {
+                amount: Uint128::new(400),
+                token: Token::Denom(STAKE_DENOM.to_owned()),
+            }),
+            &coins(500, STAKE_DENOM),
+        )
+        .unwrap();
+    assert_eq!(suite.balance(MANAGER, STAKE_DENOM), 100);
+    assert_eq!(suite.pending(ALICE, STAKE_DENOM), 400);
+}
+
+#[test]
+fn rejects_missing_insufficient_or_mixed_funds() {
+    let mut suite = setup(instantiate_msg()).unwrap();
+    suite.mint(ALICE, 1_000, STAKE_DENOM);
+    suite.mint(ALICE, 1_000, "uother");
+
+    assert_err(
+        suite.execute(ALICE, &stake_msg(1_000), &[]),
+        "expected 1000",
+    );
+    assert_err(
+        suite.execute(ALICE, &stake_msg(1_000), &coins(999, STAKE_DENOM)),
+        "insufficient funds: expected 1000, received 999",
+    );
+    assert_err(
+        suite.execute(
+            ALICE,
+            &stake_msg(1_000),
+            &[coin(1_000, STAKE_DENOM), coin(1_000, "uother")],
+        ),
+        "unexpected denoms in funds",
+    );
+    assert_err(
+        suite.execute(ALICE, &stake_msg(0), &coins(1_000, STAKE_DENOM)),
+        "amount must be greater than zero",
+    );
+
+    assert!(suite.account(ALICE).is_none());
+    assert_eq!(suite.balance(ALICE, STAKE_DENOM), 1_000);
+}
@@ -0,0 +1,268 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, DepsMut, Env, Response, Uint128, Uint64};
+use cw_multi_test::{error::AnyResult, AppResponse, ContractWrapper, Executor};
+use cw_sath::{
+    contract::{execute, instantiate, migrate, query, sudo},
+    error::ContractError,
+    msg::{AdminMsg, AmortizationConfig, ExecuteMsg, MigrateMsg, QueryMsg},
+    responses::CrankResponse,
+    state::{
+        models::{Account, BalanceEvent, StakingEvent},
+        storage::{
+            AMORTIZATION_QUEUE, BALANCES, N_ACCOUNTS, N_BALANCE_EVENTS, REVENUE_TOKEN_KEYS, SEQ_NO,
+            TOTAL_DELEGATION, TOTAL_WEIGHT, TS_BALANCE, TS_STAKE,
+        },
+    },
+    token::{Token, TokenAmount},
+};
+use cw_storage_plus::{Item, Map};
+
+use crate::suite::{
+    assert_err, attribute, instantiate_msg, setup, Suite, ALICE, BOB, HOUSE_ADDR, MANAGER,
+    STAKE_DENOM,
+};
+
+/// Revenue token of the legacy event series
+pub const LEGACY_DENOM: &str = "urev";
+
+/// State written by a prior version of the contract: accounts staked at
+/// seq_no 0, followed by equal revenue events at seq_nos 1 through n_events.
+#[cw_serde]
+pub struct LegacySeed {
+    pub stakers: Vec<(Addr, Uint128)>,
+    pub n_events: u64,
+    pub event_amount: Uint128,
+}
+
+/// Migrate entrypoint of a stand-in contract that writes legacy state, as
+/// stored by the version predating reward indexes
+fn seed(
+    deps: DepsMut,
+    env: Env,
+    msg: LegacySeed,
+) -> Result<Response, ContractError> {
+    const LEGACY_ACCOUNTS: Map<&Addr, Account> = Map::new("accounts");
+    const LEGACY_X: Item<Uint64> = Item::new("x");
+
+    let token_key = Token::Denom(LEGACY_DENOM.to_owned()).to_key();
+    let mut total = Uint128::zero();
+
+    for (addr, delegation) in msg.stakers.iter() {
+        let mut account = Account::new(env.block.time, Uint64::zero(), Default::default());
+        account.delegation = *delegation;
+        LEGACY_ACCOUNTS.save(deps.storage, addr, &account)?;
+        TS_STAKE.save(
+            deps.storage,
+            (addr, 0),
+            &StakingEvent { delta: *delegation },
+        )?;
+        AMORTIZATION_QUEUE.push_back(deps.storage, addr)?;
+        total += *delegation;
+    }
+
+    let n_accounts = msg.stakers.len() as u32;
+    for seq_no in 1..=msg.n_events {
+        TS_BALANCE.save(
+            deps.storage,
+            (&token_key, seq_no),
+            &BalanceEvent {
+                delta: msg.event_amount,
+                total,
+                n_accounts,
+                ref_count: n_accounts,
+                x: Uint64::zero(),
+            },
+        )?;
+    }
+
+    N_ACCOUNTS.save(deps.storage, &n_accounts)?;
+    N_BALANCE_EVENTS.save(deps.storage, &token_key, &(msg.n_events as u32))?;
+    SEQ_NO.save(deps.storage, &Uint64::from(msg.n_events + 1))?;
+    TOTAL_DELEGATION.save(deps.storage, &total)?;
+    TOTAL_WEIGHT.remove(deps.storage);
+    REVENUE_TOKEN_KEYS.save(deps.storage, &token_key, &0)?;
+    BALANCES.save(
+        deps.storage,
+        &token_key,
+        &TokenAmount {
+            token: Token::Denom(LEGACY_DENOM.to_owned()),
+            amount: msg.event_amount * Uint128::from(msg.n_events),
+        },
+    )?;
+    LEGACY_X.save(deps.storage, &Uint64::one())?;
+
+    Ok(Response::new())
+}
+
+/// Set up a house holding the given legacy state and migrate it to the
+/// current version
+pub fn setup_legacy(msg: LegacySeed) -> Suite {
+    let mut suite = setup(instantiate_msg()).unwrap();
+
+    let seeder = suite.app.store_code(Box::new(
+        ContractWrapper::new(execute, instantiate, query).with_migrate(seed),
+    ));
+    let current = suite.app.store_code(Box::new(
+        ContractWrapper::new(execute, instantiate, query)
+            .with_sudo(sudo)
+            .with_migrate(migrate),
+    ));
+
+    let total = msg.event_amount.u128() * msg.n_events as u128;
+    let staked: u128 = msg.stakers.iter().map(|(_, d)| d.u128()).sum();
+    suite.mint(HOUSE_ADDR, total, LEGACY_DENOM);
+    suite.mint(HOUSE_ADDR, staked, STAKE_DENOM);
+
+    let manager = Addr::unchecked(MANAGER);
+    let house = suite.house.to_owned();
+    suite
+        .app
+        .migrate_contract(manager.to_owned(), house.to_owned(), &msg, seeder)
+        .unwrap();
+    suite
+        .app
+        .migrate_contract(manager, house, &MigrateMsg {}, current)
+        .unwrap();
+
+    suite
+}
+
+pub fn seed_msg(n_events: u64) -> LegacySeed {
+    LegacySeed {
+        stakers: vec![
+            (Addr::unchecked(ALICE), Uint128::new(1_000)),
+            (Addr::unchecked(BOB), Uint128::new(3_000)),
+        ],
+        n_events,
+        event_amount: Uint128::new(4),
+    }
+}
+
+pub fn n_pending_events(suite: &Suite) -> u32 {
+    suite
+        .query::<CrankResponse>(&QueryMsg::Crank {})
+        .n_pending_events
+}
+
+/// Crank until every legacy event has been synced by every account
+pub fn crank_legacy(suite: &mut Suite) {
+    while n_pending_events(suite) > 0 {
+        suite
+            .execute(ALICE, &ExecuteMsg::Crank { limit: None }, &[])
+            .unwrap();
+    }
+}
+
+fn sync(
+    suite: &mut Suite,
+    address: &str,
+    max_events: u32,
+) -> AnyResult<AppResponse> {
+    suite.execute(
+        BOB,
+        &ExecuteMsg::SyncAccount {
+            address: Addr::unchecked(address),
+            max_events: Some(max_events),
+        },
+        &[],
+    )
+}
+
+#[test]
+fn legacy_and_index_revenue_accrue_alike() {
+    // 250 events of 4 revenue, split 1:3, more than an account syncs at once
+    let mut suite = setup_legacy(seed_msg(250));
+
+    suite.deposit(MANAGER, 400, LEGACY_DENOM).unwrap();
+
+    assert_eq!(suite.pending(ALICE, LEGACY_DENOM), 250 + 100);
+    assert_eq!(suite.pending(BOB, LEGACY_DENOM), 750 + 300);
+}
+
+#[test]
+fn dormant_accounts_change_weight_before_legacy_sync() {
+    let mut suite = setup_legacy(seed_msg(250));
+
+    // Alice doubles her stake with legacy events still left to sync
+    suite.stake(ALICE, 1_000).unwrap();
+    assert!(n_pending_events(&suite) > 0);
+
+    suite.deposit(MANAGER, 500, LEGACY_DENOM).unwrap();
+    crank_legacy(&mut suite);
+
+    // Legacy revenue is still split 1:3, while new revenue is split 2:3
+    assert_eq!(suite.pending(ALICE, LEGACY_DENOM), 250 + 200);
+    assert_eq!(suite.pending(BOB, LEGACY_DENOM), 750 + 300);
+
+    suite.claim(BOB).unwrap();
+    assert_eq!(suite.balance(BOB, LEGACY_DENOM), 1_050);
+}
+
+#[test]
+fn syncs_long_histories_in_bounded_steps() {
+    let mut suite = setup_legacy(seed_msg(250));
+
+    // Anyone can advance an account's sync
+    for (n_events, complete) in [("100", "false"), ("100", "false"), ("50", "true")] {
+        let resp = sync(&mut suite, ALICE, 100).unwrap();
+        assert_eq!(attribute(&resp, "n_events").unwrap(), n_events);
+        assert_eq!(attribute(&resp, "complete").unwrap(), complete);
+    }
+
+    let resp = sync(&mut suite, ALICE, 100).unwrap();
+    assert_eq!(attribute(&resp, "n_events").unwrap(), "0");
+    assert_eq!(suite.pending(ALICE, LEGACY_DENOM), 250);
+}
+
+#[test]
+fn claims_and_unstakes_require_a_sync_first() {
+    let mut suite = setup_legacy(seed_msg(250));
+
+    assert_err(sync(&mut suite, MANAGER, 100), "Account not found");
+
+    // More events are pending than a claim or unstake syncs
+    assert_err(suite.claim(ALICE), "execute SyncAccount first");
+    assert_err(suite.unstake(ALICE, 100), "execute SyncAccount first");
+    assert_eq!(suite.balance(ALICE, LEGACY_DENOM), 0);
+
+    sync(&mut suite, ALICE, 200).unwrap();
+    suite.claim(ALICE).unwrap();
+    suite.unstake(ALICE, 100).unwrap();
+    assert_eq!(suite.balance(ALICE, LEGACY_DENOM), 250);
+    assert_eq!(suite.pending(ALICE, LEGACY_DENOM), 0);
+}
+
+#[test]
+fn amortization_shares_one_event_budget_across_accounts() {
+    let stakers: Vec<String> = (0..10).map(|i| format!("staker{}", i)).collect();
+    let mut suite = setup_legacy(LegacySeed {
+        stakers: stakers
+            .iter()
+            .map(|s| (Addr::unchecked(s), Uint128::new(100)))
+            .collect(),
+        n_events: 50,
+        event_amount: Uint128::new(10),
+    });
+    suite
+        .admin(AdminMsg::SetAmortizationConfig {
+            config: AmortizationConfig {
+                batch_size: 10,
+                account_pct: Uint128::new(1_000_000),
+            },
+        })
+        .unwrap();
+
+    // A deposit amortizes all 10 accounts, but syncs only 100 of their 500
+    // legacy events between them
+    suite.deposit(MANAGER, 100, LEGACY_DENOM).unwrap();
+
+    let mut n_remaining = 0;
+    for staker in stakers.iter() {
+        let resp = sync(&mut suite, staker, 500).unwrap();
+        n_remaining += attribute(&resp, "n_events")
+            .unwrap()
+            .parse::<u32>()
+            .unwrap();
+    }
+    assert_eq!(n_remaining, 500 - 100);
+}
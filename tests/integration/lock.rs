@@ -0,0 +1,87 @@
+use cosmwasm_std::{coins, Addr, Uint128, Uint64};
+use cw_multi_test::{error::AnyResult, AppResponse};
+use cw_sath::msg::{ExecuteMsg, LockTier, StakeMsg};
+
+use crate::suite::{assert_err, instantiate_msg, setup, Suite, ALICE, BOB, MANAGER, STAKE_DENOM};
+
+const LOCK_SECONDS: u64 = 100;
+
+fn setup_locks() -> Suite {
+    let mut msg = instantiate_msg();
+    msg.staking.lock_tiers = Some(vec![LockTier {
+        seconds: Uint64::new(LOCK_SECONDS),
+        multiplier: Uint128::new(2_000_000),
+    }]);
+    setup(msg).unwrap()
+}
+
+/// Stake for the given address, locked for LOCK_SECONDS
+fn stake_locked(
+    suite: &mut Suite,
+    sender: &str,
+    address: &str,
+    amount: u128,
+) -> AnyResult<AppResponse> {
+    suite.mint(sender, amount, STAKE_DENOM);
+    suite.execute(
+        sender,
+        &ExecuteMsg::Stake(StakeMsg {
+            amount: Uint128::new(amount),
+            address: Some(Addr::unchecked(address)),
+            lock_seconds: Some(Uint64::new(LOCK_SECONDS)),
+        }),
+        &coins(amount, STAKE_DENOM),
+    )
+}
+
+#[test]
+fn crank_releases_expired_locks_of_dormant_accounts() {
+    let mut suite = setup_locks();
+    stake_locked(&mut suite, ALICE, ALICE, 1_000).unwrap();
+    suite.stake(BOB, 1_000).unwrap();
+
+    // Alice's locked stake counts double while locked
+    suite.deposit(MANAGER, 300, STAKE_DENOM).unwrap();
+    assert_eq!(suite.pending(ALICE, STAKE_DENOM), 200);
+    assert_eq!(suite.pending(BOB, STAKE_DENOM), 100);
+
+    // Once the lock expires, a crank releases it without Alice acting
+    suite
+        .app
+        .update_block(|block| block.time = block.time.plus_seconds(LOCK_SECONDS));
+    suite
+        .execute(BOB, &ExecuteMsg::Crank { limit: None }, &[])
+        .unwrap();
+    assert_eq!(suite.account(ALICE).unwrap().weight, Uint128::new(1_000));
+
+    suite.deposit(MANAGER, 200, STAKE_DENOM).unwrap();
+    assert_eq!(suite.pending(ALICE, STAKE_DENOM), 300);
+    assert_eq!(suite.pending(BOB, STAKE_DENOM), 200);
+}
+
+#[test]
+fn rejects_locking_stake_on_behalf_of_another_account() {
+    let mut suite = setup_locks();
+    assert_err(
+        stake_locked(&mut suite, BOB, ALICE, 1_000),
+        "only the staker can lock their stake",
+    );
+    assert!(suite.account(ALICE).is_none());
+}
+
+#[test]
+fn locked_stake_unstakes_only_once_unlocked() {
+    let mut suite = setup_locks();
+    stake_locked(&mut suite, ALICE, ALICE, 1_000).unwrap();
+
+    assert_err(
+        suite.unstake(ALICE, 1_000),
+        "amount exceeds unlocked delegation",
+    );
+    assert_eq!(suite.account(ALICE).unwrap().weight, Uint128::new(2_000));
+
+    suite
+        .app
+        .update_block(|block| block.time = block.time.plus_seconds(LOCK_SECONDS));
+    suite.unstake(ALICE, 1_000).unwrap();
+}
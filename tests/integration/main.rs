@@ -0,0 +1,20 @@
+mod accounts;
+mod admin;
+mod burn;
+mod claim;
+mod clock;
+mod close;
+mod compound;
+mod crank;
+mod cw20;
+mod deposits;
+mod funds;
+mod legacy;
+mod lock;
+mod manager;
+mod payout;
+mod slash;
+mod stake;
+mod suite;
+mod taxes;
+mod unbonding;
@@ -0,0 +1,84 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw_sath::{
+    msg::{AdminMsg, ExecuteMsg, QueryMsg},
+    responses::ConfigResponse,
+};
+
+use crate::suite::{assert_err, instantiate_msg, setup, Suite, ALICE, BOB, MANAGER};
+
+fn propose(
+    suite: &mut Suite,
+    address: &str,
+    expires_in: Option<u64>,
+) {
+    let expires_at = expires_in.map(|s| suite.app.block_info().time.plus_seconds(s));
+    suite
+        .execute(
+            MANAGER,
+            &ExecuteMsg::ProposeManager {
+                address: Addr::unchecked(address),
+                expires_at,
+            },
+            &[],
+        )
+        .unwrap();
+}
+
+fn managed_by(suite: &Suite) -> Addr {
+    suite
+        .query::<ConfigResponse>(&QueryMsg::Config {})
+        .managed_by
+}
+
+#[test]
+fn proposed_manager_accepts_management() {
+    let mut suite = setup(instantiate_msg()).unwrap();
+    propose(&mut suite, ALICE, None);
+
+    // The current manager remains in charge until the proposal is accepted
+    assert_eq!(managed_by(&suite), Addr::unchecked(MANAGER));
+
+    suite
+        .execute(ALICE, &ExecuteMsg::AcceptManager {}, &[])
+        .unwrap();
+    assert_eq!(managed_by(&suite), Addr::unchecked(ALICE));
+
+    let msg = ExecuteMsg::Admin(AdminMsg::SetMinIncrement {
+        amount: Uint128::new(10),
+    });
+    suite.execute(ALICE, &msg, &[]).unwrap();
+    assert_err(
+        suite.execute(MANAGER, &msg, &[]),
+        "only the contract manager can perform this action",
+    );
+}
+
+#[test]
+fn rejects_acceptance_by_others_after_expiry_or_cancellation() {
+    let mut suite = setup(instantiate_msg()).unwrap();
+    propose(&mut suite, ALICE, Some(60));
+
+    assert_err(
+        suite.execute(BOB, &ExecuteMsg::AcceptManager {}, &[]),
+        "only the proposed manager can accept",
+    );
+
+    suite
+        .app
+        .update_block(|block| block.time = block.time.plus_seconds(60));
+    assert_err(
+        suite.execute(ALICE, &ExecuteMsg::AcceptManager {}, &[]),
+        "manager proposal expired",
+    );
+
+    propose(&mut suite, ALICE, None);
+    suite
+        .execute(MANAGER, &ExecuteMsg::CancelManagerProposal {}, &[])
+        .unwrap();
+    assert_err(
+        suite.execute(ALICE, &ExecuteMsg::AcceptManager {}, &[]),
+        "no pending manager proposal",
+    );
+
+    assert_eq!(managed_by(&suite), Addr::unchecked(MANAGER));
+}
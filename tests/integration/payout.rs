@@ -0,0 +1,125 @@
+use cosmwasm_std::{Addr, Uint128, Uint64};
+use cw_multi_test::{error::AnyResult, AppResponse};
+use cw_sath::{
+    msg::{AdminMsg, ClaimMsg, ExecuteMsg, PayoutConfig},
+    token::Token,
+};
+
+use crate::suite::{assert_err, instantiate_msg, setup, Suite, ALICE, BOB, MANAGER, STAKE_DENOM};
+
+const GAME: &str = "game";
+const WINNER: &str = "winner";
+
+/// Set up a house whose game can pay out up to the given share of delegation
+fn setup_payouts(max_payout_pct: u128) -> Suite {
+    let mut suite = setup(instantiate_msg()).unwrap();
+    suite
+        .admin(AdminMsg::AddGame {
+            address: Addr::unchecked(GAME),
+        })
+        .unwrap();
+    suite
+        .admin(AdminMsg::SetPayoutConfig {
+            config: Some(PayoutConfig {
+                max_payout_pct: Uint128::new(max_payout_pct),
+                window_seconds: Uint64::new(1),
+                max_window_pct: Uint128::new(999_999),
+            }),
+        })
+        .unwrap();
+    suite
+}
+
+/// Pay the winner in a new payout window
+fn payout(
+    suite: &mut Suite,
+    amount: u128,
+) -> AnyResult<AppResponse> {
+    suite
+        .app
+        .update_block(|block| block.time = block.time.plus_seconds(1));
+    suite.execute(
+        GAME,
+        &ExecuteMsg::RequestPayout {
+            recipient: Addr::unchecked(WINNER),
+            token: Token::Denom(STAKE_DENOM.to_owned()),
+            amount: Uint128::new(amount),
+        },
+        &[],
+    )
+}
+
+fn delegation(
+    suite: &Suite,
+    address: &str,
+) -> u128 {
+    suite.account(address).unwrap().delegation.u128()
+}
+
+#[test]
+fn payouts_reduce_delegation_pro_rata() {
+    let mut suite = setup_payouts(500_000);
+    suite.stake(ALICE, 1_000).unwrap();
+    suite.stake(BOB, 3_000).unwrap();
+
+    payout(&mut suite, 2_000).unwrap();
+
+    assert_eq!(suite.balance(WINNER, STAKE_DENOM), 2_000);
+    assert_eq!(delegation(&suite, ALICE), 500);
+    assert_eq!(delegation(&suite, BOB), 1_500);
+
+    suite.deposit(MANAGER, 400, STAKE_DENOM).unwrap();
+    assert_eq!(suite.pending(ALICE, STAKE_DENOM), 100);
+    assert_eq!(suite.pending(BOB, STAKE_DENOM), 300);
+}
+
+#[test]
+fn delegation_scale_rebases_instead_of_vanishing() {
+    let mut suite = setup_payouts(999_000);
+    let stake = 10u128.pow(24);
+    suite.stake(ALICE, stake).unwrap();
+    suite.stake(BOB, 3 * stake).unwrap();
+
+    // Each payout leaves a thousandth of delegation, so seven of them reduce
+    // it by a factor of 10^21, more than the precision of the scale
+    let mut total = 4 * stake;
+    for _ in 0..7 {
+        payout(&mut suite, total / 1_000 * 999).unwrap();
+        total /= 1_000;
+        suite.deposit(MANAGER, 4_000_000, STAKE_DENOM).unwrap();
+    }
+
+    assert_eq!(delegation(&suite, ALICE), 1_000);
+    assert_eq!(delegation(&suite, BOB), 3_000);
+    assert_eq!(suite.pending(ALICE, STAKE_DENOM), 7_000_000);
+    assert_eq!(suite.pending(BOB, STAKE_DENOM), 21_000_000);
+
+    // Accounts keep accruing correctly once rescaled past the rebases
+    suite.stake(ALICE, 2_000).unwrap();
+    assert_eq!(delegation(&suite, ALICE), 3_000);
+    suite.deposit(MANAGER, 6_000_000, STAKE_DENOM).unwrap();
+    assert_eq!(suite.pending(ALICE, STAKE_DENOM), 10_000_000);
+    assert_eq!(suite.pending(BOB, STAKE_DENOM), 24_000_000);
+
+    suite
+        .execute(
+            BOB,
+            &ExecuteMsg::Claim(ClaimMsg {
+                token: None,
+                recipient: None,
+                msg: None,
+            }),
+            &[],
+        )
+        .unwrap();
+    assert_eq!(suite.balance(BOB, STAKE_DENOM), 24_000_000);
+}
+
+#[test]
+fn rejects_payouts_over_the_max_share_of_delegation() {
+    let mut suite = setup_payouts(500_000);
+    suite.stake(ALICE, 1_000).unwrap();
+
+    assert_err(payout(&mut suite, 501), "payout exceeds max of 500");
+    assert_eq!(delegation(&suite, ALICE), 1_000);
+}
@@ -0,0 +1,124 @@
+use cosmwasm_std::{coins, Addr, Uint128, Uint64};
+use cw_sath::{
+    msg::{AdminMsg, ExecuteMsg, StakeMsg},
+    token::Token,
+};
+
+use crate::suite::{
+    assert_err, instantiate_msg, setup, Suite, ALICE, BOB, HOUSE_ADDR, STAKE_DENOM,
+};
+
+const TREASURY: &str = "treasury";
+
+/// Staking token created by the house, which it can burn
+const BURNABLE_DENOM: &str = "factory/contract0/ustake";
+
+/// Set up a house whose stakers each have unbonding tranches of 400 out of
+/// 1000 staked, with slashed stake sent to the treasury
+fn setup_unbonding() -> Suite {
+    let mut msg = instantiate_msg();
+    msg.staking.unbonding_seconds = Some(Uint64::new(100));
+    let mut suite = setup(msg).unwrap();
+
+    for staker in [ALICE, BOB] {
+        suite.stake(staker, 1_000).unwrap();
+        suite.unstake(staker, 400).unwrap();
+    }
+
+    suite
+        .admin(AdminMsg::SetSlashRecipient {
+            address: Some(Addr::unchecked(TREASURY)),
+        })
+        .unwrap();
+    suite
+}
+
+fn delegation_and_unbonding(
+    suite: &Suite,
+    address: &str,
+) -> (u128, u128) {
+    let account = suite.account(address).unwrap();
+    let unbonding = account.unbondings.iter().map(|u| u.amount.u128()).sum();
+    (account.delegation.u128(), unbonding)
+}
+
+#[test]
+fn slashing_an_account_slashes_its_unbonding_proportionally() {
+    let mut suite = setup_unbonding();
+
+    suite
+        .admin(AdminMsg::SlashAccount {
+            address: Addr::unchecked(ALICE),
+            amount: Uint128::new(300),
+        })
+        .unwrap();
+
+    assert_eq!(delegation_and_unbonding(&suite, ALICE), (300, 200));
+    assert_eq!(delegation_and_unbonding(&suite, BOB), (600, 400));
+    assert_eq!(suite.balance(TREASURY, STAKE_DENOM), 500);
+}
+
+#[test]
+fn slashing_every_account_slashes_unbonding_by_the_same_rate() {
+    let mut suite = setup_unbonding();
+
+    suite
+        .admin(AdminMsg::Slash {
+            ratio: Uint128::new(250_000),
+        })
+        .unwrap();
+
+    assert_eq!(delegation_and_unbonding(&suite, ALICE), (450, 300));
+    assert_eq!(delegation_and_unbonding(&suite, BOB), (450, 300));
+    assert_eq!(suite.balance(TREASURY, STAKE_DENOM), 500);
+}
+
+#[test]
+fn burns_slashed_stake_without_a_recipient() {
+    let mut msg = instantiate_msg();
+    msg.staking.staking_token = Token::Denom(BURNABLE_DENOM.to_owned());
+    msg.staking.revenue_tokens = vec![Token::Denom(BURNABLE_DENOM.to_owned())];
+    let mut suite = setup(msg).unwrap();
+
+    suite.mint(ALICE, 1_000, BURNABLE_DENOM);
+    suite
+        .execute(
+            ALICE,
+            &ExecuteMsg::Stake(StakeMsg {
+                amount: Uint128::new(1_000),
+                address: None,
+                lock_seconds: None,
+            }),
+            &coins(1_000, BURNABLE_DENOM),
+        )
+        .unwrap();
+
+    suite
+        .admin(AdminMsg::SlashAccount {
+            address: Addr::unchecked(ALICE),
+            amount: Uint128::new(100),
+        })
+        .unwrap();
+
+    assert_eq!(suite.account(ALICE).unwrap().delegation.u128(), 900);
+    assert_eq!(suite.balance(HOUSE_ADDR, BURNABLE_DENOM), 900);
+}
+
+#[test]
+fn requires_a_slash_recipient_for_unburnable_staking_tokens() {
+    let mut suite = setup(instantiate_msg()).unwrap();
+    suite.stake(ALICE, 1_000).unwrap();
+
+    assert_err(
+        suite.admin(AdminMsg::SlashAccount {
+            address: Addr::unchecked(ALICE),
+            amount: Uint128::new(100),
+        }),
+        "a slash recipient is required",
+    );
+    assert_err(
+        suite.admin(AdminMsg::SetSlashRecipient { address: None }),
+        "a slash recipient is required",
+    );
+    assert_eq!(suite.account(ALICE).unwrap().delegation.u128(), 1_000);
+}
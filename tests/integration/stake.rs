@@ -0,0 +1,49 @@
+use cosmwasm_std::Uint128;
+use cw_sath::{
+    msg::{AdminMsg, QueryMsg},
+    responses::ConfigResponse,
+};
+
+use crate::suite::{assert_err, instantiate_msg, setup, ALICE, BOB, STAKE_DENOM};
+
+#[test]
+fn stakes_at_least_the_min_increment() {
+    let mut msg = instantiate_msg();
+    msg.staking.min_increment = Some(Uint128::new(100));
+    let mut suite = setup(msg).unwrap();
+
+    let config = suite.query::<ConfigResponse>(&QueryMsg::Config {});
+    assert_eq!(config.min_increment, Uint128::new(100));
+
+    suite.stake(ALICE, 100).unwrap();
+    suite.stake(ALICE, 250).unwrap();
+    assert_eq!(suite.account(ALICE).unwrap().delegation, Uint128::new(350));
+
+    // Lowering the minimum admits smaller stakes
+    suite
+        .admin(AdminMsg::SetMinIncrement {
+            amount: Uint128::new(10),
+        })
+        .unwrap();
+    suite.stake(BOB, 10).unwrap();
+    assert_eq!(suite.account(BOB).unwrap().delegation, Uint128::new(10));
+}
+
+#[test]
+fn rejects_stakes_below_the_min_increment() {
+    let mut msg = instantiate_msg();
+    msg.staking.min_increment = Some(Uint128::new(100));
+    let mut suite = setup(msg).unwrap();
+
+    assert_err(
+        suite.stake(ALICE, 99),
+        "99 is less than the minimum increment 100",
+    );
+    assert!(suite.account(ALICE).is_none());
+    assert_eq!(suite.balance(ALICE, STAKE_DENOM), 99);
+
+    // Existing stakers are held to the minimum too
+    suite.stake(BOB, 100).unwrap();
+    assert_err(suite.stake(BOB, 1), "less than the minimum increment");
+    assert_eq!(suite.account(BOB).unwrap().delegation, Uint128::new(100));
+}
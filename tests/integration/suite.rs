@@ -0,0 +1,365 @@
+use std::fmt::Debug;
+
+use cosmwasm_std::{
+    coins,
+    testing::{MockApi, MockStorage},
+    Addr, Api, BankMsg, Binary, BlockInfo, Coin, CustomQuery, Empty, Storage, Uint128,
+};
+use cw_multi_test::{
+    error::{bail, AnyError, AnyResult},
+    AppBuilder, AppResponse, BankKeeper, BankSudo, ContractWrapper, CosmosRouter,
+    DistributionKeeper, Executor, FailingModule, GovFailingModule, IbcFailingModule, StakeKeeper,
+    Stargate, WasmKeeper,
+};
+use cw_sath::{
+    contract::{execute, instantiate, query, sudo},
+    msg::{
+        AdminMsg, ClaimMsg, DepositMsg, ExecuteMsg, HouseMarketingInfo, InstantiateMsg, QueryMsg,
+        StakeMsg, StakingConfig, UnstakeMsg,
+    },
+    responses::AccountResponse,
+    token::{Token, TOKENFACTORY_MSG_BURN_TYPE_URL},
+};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+
+pub const MANAGER: &str = "manager";
+pub const ALICE: &str = "alice";
+pub const BOB: &str = "bob";
+
+/// Native staking token
+pub const STAKE_DENOM: &str = "ustake";
+
+/// Address of the first contract instantiated in a suite
+pub const HOUSE_ADDR: &str = "contract0";
+
+pub type HouseApp = cw_multi_test::App<
+    BankKeeper,
+    MockApi,
+    MockStorage,
+    FailingModule<Empty, Empty, Empty>,
+    WasmKeeper<Empty, Empty>,
+    StakeKeeper,
+    DistributionKeeper,
+    IbcFailingModule,
+    GovFailingModule,
+    TokenFactory,
+>;
+
+/// Tokenfactory stand-in that executes MsgBurn like the chain would, burning
+/// from the sender's balance only if the sender created the denom
+pub struct TokenFactory;
+
+impl Stargate for TokenFactory {
+    fn execute<ExecC, QueryC>(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        sender: Addr,
+        type_url: String,
+        value: Binary,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: Debug + Clone + PartialEq + JsonSchema + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+    {
+        if type_url != TOKENFACTORY_MSG_BURN_TYPE_URL {
+            bail!("unexpected stargate message: {}", type_url);
+        }
+
+        let fields = decode_proto_fields(&value)?;
+        let coin = decode_proto_fields(&fields[1])?;
+        let burner = String::from_utf8(fields[0].to_owned())?;
+        let denom = String::from_utf8(coin[0].to_owned())?;
+        let amount: u128 = String::from_utf8(coin[1].to_owned())?.parse()?;
+
+        if burner != sender.as_str() {
+            bail!("MsgBurn sender {} is not the signer {}", burner, sender);
+        }
+        if !denom.starts_with(&format!("factory/{}/", sender)) {
+            bail!(
+                "unauthorized account: {} is not the admin of {}",
+                sender,
+                denom
+            );
+        }
+
+        router.execute(
+            api,
+            storage,
+            block,
+            sender,
+            BankMsg::Burn {
+                amount: coins(amount, denom),
+            }
+            .into(),
+        )
+    }
+}
+
+/// Decode a protobuf message consisting only of length-delimited fields
+fn decode_proto_fields(bytes: &[u8]) -> AnyResult<Vec<Vec<u8>>> {
+    let mut fields: Vec<Vec<u8>> = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] & 7 != 2 {
+            bail!("unexpected wire type");
+        }
+        i += 1;
+        let mut len: usize = 0;
+        let mut shift = 0;
+        loop {
+            let b = bytes[i];
+            i += 1;
+            len |= ((b & 0x7f) as usize) << shift;
+            shift += 7;
+            if b < 0x80 {
+                break;
+            }
+        }
+        fields.push(bytes[i..i + len].to_vec());
+        i += len;
+    }
+    Ok(fields)
+}
+
+pub fn instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        marketing: HouseMarketingInfo {
+            logo: None,
+            name: Some("house".to_owned()),
+            description: None,
+        },
+        taxes: vec![],
+        staking: StakingConfig {
+            staking_token: Token::Denom(STAKE_DENOM.to_owned()),
+            revenue_tokens: vec![Token::Denom(STAKE_DENOM.to_owned())],
+            min_increment: None,
+            unbonding_seconds: None,
+            lock_tiers: None,
+        },
+        burn_pct: None,
+    }
+}
+
+pub struct Suite {
+    pub app: HouseApp,
+    pub house: Addr,
+}
+
+pub fn new_app() -> HouseApp {
+    AppBuilder::new()
+        .with_stargate(TokenFactory)
+        .build(|_, _, _| {})
+}
+
+pub fn setup(msg: InstantiateMsg) -> AnyResult<Suite> {
+    setup_in(new_app(), msg)
+}
+
+/// Instantiate a house in an app that may already have other contracts
+pub fn setup_in(
+    mut app: HouseApp,
+    msg: InstantiateMsg,
+) -> AnyResult<Suite> {
+    let code_id = app.store_code(Box::new(
+        ContractWrapper::new(execute, instantiate, query).with_sudo(sudo),
+    ));
+    let house = app.instantiate_contract(
+        code_id,
+        Addr::unchecked(MANAGER),
+        &msg,
+        &[],
+        "house",
+        Some(MANAGER.to_owned()),
+    )?;
+
+    Ok(Suite { app, house })
+}
+
+impl Suite {
+    pub fn mint(
+        &mut self,
+        address: &str,
+        amount: u128,
+        denom: &str,
+    ) {
+        self.app
+            .sudo(
+                BankSudo::Mint {
+                    to_address: address.to_owned(),
+                    amount: coins(amount, denom),
+                }
+                .into(),
+            )
+            .unwrap();
+    }
+
+    pub fn execute(
+        &mut self,
+        sender: &str,
+        msg: &ExecuteMsg,
+        funds: &[Coin],
+    ) -> AnyResult<AppResponse> {
+        self.app
+            .execute_contract(Addr::unchecked(sender), self.house.to_owned(), msg, funds)
+    }
+
+    pub fn admin(
+        &mut self,
+        msg: AdminMsg,
+    ) -> AnyResult<AppResponse> {
+        self.execute(MANAGER, &ExecuteMsg::Admin(msg), &[])
+    }
+
+    /// Mint staking tokens to the sender and stake them
+    pub fn stake(
+        &mut self,
+        sender: &str,
+        amount: u128,
+    ) -> AnyResult<AppResponse> {
+        self.mint(sender, amount, STAKE_DENOM);
+        self.execute(
+            sender,
+            &ExecuteMsg::Stake(StakeMsg {
+                amount: Uint128::new(amount),
+                address: None,
+                lock_seconds: None,
+            }),
+            &coins(amount, STAKE_DENOM),
+        )
+    }
+
+    /// Unstake the amount from the sender's delegation, without skipping
+    /// unbonding
+    pub fn unstake(
+        &mut self,
+        sender: &str,
+        amount: u128,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            &ExecuteMsg::Unstake(UnstakeMsg {
+                amount: Some(Uint128::new(amount)),
+                address: None,
+                instant: None,
+            }),
+            &[],
+        )
+    }
+
+    /// Claim every pending token and matured unbonding to the sender
+    pub fn claim(
+        &mut self,
+        sender: &str,
+    ) -> AnyResult<AppResponse> {
+        self.execute(
+            sender,
+            &ExecuteMsg::Claim(ClaimMsg {
+                token: None,
+                recipient: None,
+                msg: None,
+            }),
+            &[],
+        )
+    }
+
+    /// Mint native tokens to the sender and deposit them
+    pub fn deposit(
+        &mut self,
+        sender: &str,
+        amount: u128,
+        denom: &str,
+    ) -> AnyResult<AppResponse> {
+        self.mint(sender, amount, denom);
+        self.execute(
+            sender,
+            &ExecuteMsg::Deposit(DepositMsg {
+                amount: Uint128::new(amount),
+                token: Token::Denom(denom.to_owned()),
+            }),
+            &coins(amount, denom),
+        )
+    }
+
+    pub fn query<T: DeserializeOwned>(
+        &self,
+        msg: &QueryMsg,
+    ) -> T {
+        self.app
+            .wrap()
+            .query_wasm_smart(self.house.to_owned(), msg)
+            .unwrap()
+    }
+
+    pub fn account(
+        &self,
+        address: &str,
+    ) -> Option<AccountResponse> {
+        self.query(&QueryMsg::Account {
+            address: Addr::unchecked(address),
+        })
+    }
+
+    /// Pending revenue of the given denom in an account's balances
+    pub fn pending(
+        &self,
+        address: &str,
+        denom: &str,
+    ) -> u128 {
+        self.account(address)
+            .map(|a| {
+                a.balances
+                    .iter()
+                    .filter(|b| b.token == Token::Denom(denom.to_owned()))
+                    .map(|b| b.amount.u128())
+                    .sum()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn balance(
+        &self,
+        address: &str,
+        denom: &str,
+    ) -> u128 {
+        self.app
+            .wrap()
+            .query_balance(address, denom)
+            .unwrap()
+            .amount
+            .u128()
+    }
+}
+
+/// Value of an attribute of the house's wasm event, if any
+pub fn attribute(
+    resp: &AppResponse,
+    key: &str,
+) -> Option<String> {
+    resp.events
+        .iter()
+        .filter(|e| e.ty == "wasm")
+        .flat_map(|e| e.attributes.iter())
+        .find(|a| a.key == key)
+        .map(|a| a.value.to_owned())
+}
+
+/// Assert that the execution failed with an error containing the fragment
+pub fn assert_err<T>(
+    result: Result<T, AnyError>,
+    fragment: &str,
+) {
+    let err = match result {
+        Ok(_) => panic!("expected an error containing {:?}", fragment),
+        Err(err) => err.root_cause().to_string(),
+    };
+    assert!(
+        err.contains(fragment),
+        "{:?} doesn't contain {:?}",
+        err,
+        fragment
+    );
+}
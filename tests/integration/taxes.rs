@@ -0,0 +1,312 @@
+use cosmwasm_std::{Addr, Uint128};
+use cw_multi_test::{error::AnyResult, AppResponse};
+use cw_sath::{
+    msg::{AdminMsg, ExecuteMsg, InstantiateMsg, QueryMsg, TaxRecipientInitArgs},
+    responses::{TaxRecipientResponseItem, TaxesResponse},
+    token::Token,
+};
+
+use crate::suite::{
+    assert_err, instantiate_msg, setup, Suite, ALICE, BOB, HOUSE_ADDR, MANAGER, STAKE_DENOM,
+};
+
+const TREASURY: &str = "treasury";
+const CHARITY: &str = "charity";
+
+/// Revenue token distinct from the staking token
+const REV_DENOM: &str = "urev";
+
+fn tax_args(
+    address: &str,
+    pct: u128,
+    autosend: bool,
+    immutable: bool,
+) -> TaxRecipientInitArgs {
+    TaxRecipientInitArgs {
+        address: Addr::unchecked(address),
+        name: None,
+        logo: None,
+        pct: Uint128::new(pct),
+        autosend,
+        immutable,
+    }
+}
+
+/// Set up a house taxing 10% of revenue for the given recipients, with Alice
+/// staked so that deposits are distributed
+fn setup_taxes(taxes: Vec<TaxRecipientInitArgs>) -> Suite {
+    let mut msg: InstantiateMsg = instantiate_msg();
+    msg.taxes = taxes;
+    msg.staking
+        .revenue_tokens
+        .push(Token::Denom(REV_DENOM.to_owned()));
+
+    let mut suite = setup(msg).unwrap();
+    suite.stake(ALICE, 1_000).unwrap();
+    suite
+}
+
+fn recipient(
+    suite: &Suite,
+    address: &str,
+) -> Option<TaxRecipientResponseItem> {
+    suite
+        .query::<TaxesResponse>(&QueryMsg::Taxes {})
+        .recipients
+        .into_iter()
+        .find(|r| r.address == address)
+}
+
+fn claim_taxes(
+    suite: &mut Suite,
+    sender: &str,
+    tokens: Option<Vec<Token>>,
+) -> AnyResult<AppResponse> {
+    suite.execute(sender, &ExecuteMsg::ClaimTaxes { tokens }, &[])
+}
+
+#[test]
+fn recipient_claims_pending_balances() {
+    let mut suite = setup_taxes(vec![tax_args(TREASURY, 100_000, false, false)]);
+
+    suite.deposit(MANAGER, 1_000, STAKE_DENOM).unwrap();
+    suite.deposit(MANAGER, 500, REV_DENOM).unwrap();
+    assert_eq!(suite.pending(ALICE, STAKE_DENOM), 900);
+
+    // Only the requested token is paid out
+    let res = claim_taxes(
+        &mut suite,
+        TREASURY,
+        Some(vec![Token::Denom(REV_DENOM.to_owned())]),
+    )
+    .unwrap();
+    let wasm = res.events.iter().find(|e| e.ty == "wasm").unwrap();
+    assert!(wasm
+        .attributes
+        .iter()
+        .any(|a| a.key == "amount" && a.value == "50"));
+    assert_eq!(suite.balance(TREASURY, REV_DENOM), 50);
+    assert_eq!(suite.balance(TREASURY, STAKE_DENOM), 0);
+
+    claim_taxes(&mut suite, TREASURY, None).unwrap();
+    assert_eq!(suite.balance(TREASURY, STAKE_DENOM), 100);
+
+    let treasury = recipient(&suite, TREASURY).unwrap();
+    assert!(treasury.totals.iter().all(|t| t.balance.is_zero()));
+    assert_eq!(
+        treasury.totals.iter().map(|t| t.total.u128()).sum::<u128>(),
+        150
+    );
+
+    // Nothing is left to claim, and the house still holds Alice's stake and
+    // revenue
+    let res = claim_taxes(&mut suite, TREASURY, None).unwrap();
+    assert!(res.events.iter().all(|e| e.ty != "transfer"));
+    assert_eq!(suite.balance(HOUSE_ADDR, STAKE_DENOM), 1_000 + 900);
+    assert_eq!(suite.balance(HOUSE_ADDR, REV_DENOM), 450);
+}
+
+#[test]
+fn autosend_taxes_are_paid_on_deposit() {
+    let mut suite = setup_taxes(vec![tax_args(TREASURY, 100_000, true, false)]);
+
+    suite.deposit(MANAGER, 1_000, REV_DENOM).unwrap();
+    assert_eq!(suite.balance(TREASURY, REV_DENOM), 100);
+
+    claim_taxes(&mut suite, TREASURY, None).unwrap();
+    assert_eq!(suite.balance(TREASURY, REV_DENOM), 100);
+}
+
+#[test]
+fn rejects_claims_from_others() {
+    let mut suite = setup_taxes(vec![tax_args(TREASURY, 100_000, false, false)]);
+    suite.deposit(MANAGER, 1_000, REV_DENOM).unwrap();
+
+    assert_err(
+        claim_taxes(&mut suite, BOB, None),
+        "sender is not a tax recipient",
+    );
+    assert_eq!(suite.balance(BOB, REV_DENOM), 0);
+}
+
+#[test]
+fn manager_adds_updates_and_removes_recipients() {
+    let mut suite = setup_taxes(vec![tax_args(TREASURY, 100_000, false, true)]);
+
+    suite
+        .admin(AdminMsg::AddTaxRecipient(tax_args(
+            CHARITY, 200_000, false, false,
+        )))
+        .unwrap();
+    suite.deposit(MANAGER, 1_000, REV_DENOM).unwrap();
+    assert_eq!(suite.pending(ALICE, REV_DENOM), 700);
+
+    suite
+        .admin(AdminMsg::UpdateTaxRecipient {
+            address: Addr::unchecked(CHARITY),
+            pct: Some(Uint128::new(50_000)),
+            autosend: Some(true),
+            immutable: None,
+        })
+        .unwrap();
+    suite.deposit(MANAGER, 1_000, REV_DENOM).unwrap();
+    assert_eq!(suite.balance(CHARITY, REV_DENOM), 50);
+    assert_eq!(suite.pending(ALICE, REV_DENOM), 700 + 850);
+
+    // Removal settles the balance accrued before autosend was enabled
+    suite
+        .admin(AdminMsg::RemoveTaxRecipient {
+            address: Addr::unchecked(CHARITY),
+        })
+        .unwrap();
+    assert_eq!(suite.balance(CHARITY, REV_DENOM), 250);
+    assert!(recipient(&suite, CHARITY).is_none());
+
+    let taxes = suite.query::<TaxesResponse>(&QueryMsg::Taxes {});
+    assert_eq!(taxes.pct, Uint128::new(100_000));
+
+    // Recipients edit their own name and logo, even while immutable
+    suite
+        .execute(
+            TREASURY,
+            &ExecuteMsg::UpdateTaxRecipientInfo {
+                name: Some("treasury".to_owned()),
+                logo: None,
+            },
+            &[],
+        )
+        .unwrap();
+    let treasury = recipient(&suite, TREASURY).unwrap();
+    assert_eq!(treasury.info.name.as_deref(), Some("treasury"));
+    assert_eq!(treasury.info.logo, None);
+}
+
+#[test]
+fn recipient_info_updates_keep_omitted_fields() {
+    let mut suite = setup_taxes(vec![tax_args(TREASURY, 100_000, false, false)]);
+    let mut update_info = |name: Option<&str>, logo: Option<&str>| {
+        suite
+            .execute(
+                TREASURY,
+                &ExecuteMsg::UpdateTaxRecipientInfo {
+                    name: name.map(str::to_owned),
+                    logo: logo.map(str::to_owned),
+                },
+                &[],
+            )
+            .unwrap();
+    };
+
+    update_info(Some("treasury"), Some("https://logo.png"));
+    update_info(Some("dao treasury"), None);
+    update_info(None, None);
+
+    let treasury = recipient(&suite, TREASURY).unwrap();
+    assert_eq!(treasury.info.name.as_deref(), Some("dao treasury"));
+    assert_eq!(treasury.info.logo.as_deref(), Some("https://logo.png"));
+}
+
+#[test]
+fn rejects_changes_to_immutable_recipients_and_excess_taxes() {
+    let mut suite = setup_taxes(vec![
+        tax_args(TREASURY, 100_000, false, true),
+        tax_args(CHARITY, 200_000, false, false),
+    ]);
+
+    assert_err(
+        suite.admin(AdminMsg::UpdateTaxRecipient {
+            address: Addr::unchecked(TREASURY),
+            pct: Some(Uint128::zero()),
+            autosend: None,
+            immutable: None,
+        }),
+        "tax recipient is immutable",
+    );
+    assert_err(
+        suite.admin(AdminMsg::RemoveTaxRecipient {
+            address: Addr::unchecked(TREASURY),
+        }),
+        "tax recipient is immutable",
+    );
+    assert_err(
+        suite.admin(AdminMsg::RemoveTaxRecipient {
+            address: Addr::unchecked(BOB),
+        }),
+        "tax recipient not found",
+    );
+    assert_err(
+        suite.admin(AdminMsg::AddTaxRecipient(tax_args(
+            CHARITY, 100_000, false, false,
+        ))),
+        "tax recipient already exists",
+    );
+    assert_err(
+        suite.admin(AdminMsg::AddTaxRecipient(tax_args(
+            BOB, 700_001, false, false,
+        ))),
+        "aggregate tax rate cannot exceed",
+    );
+    assert_err(
+        suite.admin(AdminMsg::UpdateTaxRecipient {
+            address: Addr::unchecked(CHARITY),
+            pct: Some(Uint128::new(900_001)),
+            autosend: None,
+            immutable: None,
+        }),
+        "aggregate tax rate cannot exceed",
+    );
+    assert_err(
+        suite.execute(
+            ALICE,
+            &ExecuteMsg::Admin(AdminMsg::AddTaxRecipient(tax_args(
+                BOB, 1_000, false, false,
+            ))),
+            &[],
+        ),
+        "only the contract manager can perform this action",
+    );
+    assert_err(
+        suite.execute(
+            BOB,
+            &ExecuteMsg::UpdateTaxRecipientInfo {
+                name: Some("bob".to_owned()),
+                logo: None,
+            },
+            &[],
+        ),
+        "sender is not a tax recipient",
+    );
+    assert_err(
+        suite.execute(
+            CHARITY,
+            &ExecuteMsg::UpdateTaxRecipientInfo {
+                name: Some(" ".to_owned()),
+                logo: None,
+            },
+            &[],
+        ),
+        "name must be nonempty",
+    );
+
+    // Once made immutable, a recipient can no longer be changed
+    suite
+        .admin(AdminMsg::UpdateTaxRecipient {
+            address: Addr::unchecked(CHARITY),
+            pct: None,
+            autosend: None,
+            immutable: Some(true),
+        })
+        .unwrap();
+    assert_err(
+        suite.admin(AdminMsg::UpdateTaxRecipient {
+            address: Addr::unchecked(CHARITY),
+            pct: None,
+            autosend: None,
+            immutable: Some(false),
+        }),
+        "tax recipient is immutable",
+    );
+
+    let taxes = suite.query::<TaxesResponse>(&QueryMsg::Taxes {});
+    assert_eq!(taxes.pct, Uint128::new(300_000));
+}
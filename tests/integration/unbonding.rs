@@ -0,0 +1,389 @@
+use cosmwasm_std::{Addr, Timestamp, Uint128, Uint64};
+use cw_multi_test::{error::AnyResult, AppResponse};
+use cw_sath::{
+    msg::{
+        AdminMsg, ExecuteMsg, InstantUnstakeConfig, InstantiateMsg, QueryMsg, TaxRecipientInitArgs,
+        UnstakeMsg,
+    },
+    responses::{DepositsResponse, HouseResponse},
+    state::models::AccountUnbondingState,
+    token::Token,
+};
+
+use crate::suite::{assert_err, instantiate_msg, setup, Suite, ALICE, BOB, MANAGER, STAKE_DENOM};
+
+const UNBONDING_SECONDS: u64 = 100;
+
+fn setup_unbonding() -> Suite {
+    let mut msg: InstantiateMsg = instantiate_msg();
+    msg.staking.unbonding_seconds = Some(Uint64::new(UNBONDING_SECONDS));
+    let mut suite = setup(msg).unwrap();
+    suite.stake(ALICE, 1_000).unwrap();
+    suite
+}
+
+fn wait(
+    suite: &mut Suite,
+    seconds: u64,
+) {
+    suite.app.update_block(|block| {
+        block.time = block.time.plus_seconds(seconds);
+        block.height += 1;
+    });
+}
+
+fn unbondings(suite: &Suite) -> Vec<AccountUnbondingState> {
+    suite.account(ALICE).unwrap().unbondings
+}
+
+fn cancel_unbonding(
+    suite: &mut Suite,
+    sender: &str,
+    amount: Option<u128>,
+) -> AnyResult<AppResponse> {
+    suite.execute(
+        sender,
+        &ExecuteMsg::CancelUnbonding {
+            amount: amount.map(Uint128::new),
+        },
+        &[],
+    )
+}
+
+fn instant_unstake(
+    suite: &mut Suite,
+    sender: &str,
+    address: Option<&str>,
+    amount: u128,
+) -> AnyResult<AppResponse> {
+    suite.execute(
+        sender,
+        &ExecuteMsg::Unstake(UnstakeMsg {
+            amount: Some(Uint128::new(amount)),
+            address: address.map(Addr::unchecked),
+            instant: Some(true),
+        }),
+        &[],
+    )
+}
+
+/// Enable instant unstaking with a 10% penalty
+fn enable_instant_unstake(
+    suite: &mut Suite,
+    taxed: bool,
+) {
+    suite
+        .admin(AdminMsg::SetInstantUnstakeConfig {
+            config: Some(InstantUnstakeConfig {
+                penalty_pct: Uint128::new(100_000),
+                taxed,
+            }),
+        })
+        .unwrap();
+}
+
+fn tranche(
+    amount: u128,
+    unbonds_at: Timestamp,
+) -> AccountUnbondingState {
+    AccountUnbondingState {
+        amount: Uint128::new(amount),
+        unbonds_at,
+    }
+}
+
+#[test]
+fn tranches_mature_independently() {
+    let mut suite = setup_unbonding();
+    let t0 = suite.app.block_info().time;
+
+    suite.unstake(ALICE, 300).unwrap();
+    wait(&mut suite, 60);
+    suite.unstake(ALICE, 100).unwrap();
+
+    assert_eq!(
+        unbondings(&suite),
+        vec![
+            tranche(300, t0.plus_seconds(UNBONDING_SECONDS)),
+            tranche(100, t0.plus_seconds(60 + UNBONDING_SECONDS)),
+        ]
+    );
+
+    // The later unstake doesn't delay the first tranche
+    wait(&mut suite, UNBONDING_SECONDS - 60);
+    suite.claim(ALICE).unwrap();
+    assert_eq!(suite.balance(ALICE, STAKE_DENOM), 300);
+    assert_eq!(
+        unbondings(&suite),
+        vec![tranche(100, t0.plus_seconds(60 + UNBONDING_SECONDS))]
+    );
+
+    wait(&mut suite, 60);
+    suite.claim(ALICE).unwrap();
+    assert_eq!(suite.balance(ALICE, STAKE_DENOM), 400);
+    assert!(unbondings(&suite).is_empty());
+    assert_eq!(suite.account(ALICE).unwrap().delegation, Uint128::new(600));
+}
+
+#[test]
+fn unstakes_in_one_block_share_a_tranche() {
+    let mut suite = setup_unbonding();
+    let t0 = suite.app.block_info().time;
+
+    suite.unstake(ALICE, 50).unwrap();
+    suite.unstake(ALICE, 50).unwrap();
+
+    assert_eq!(
+        unbondings(&suite),
+        vec![tranche(100, t0.plus_seconds(UNBONDING_SECONDS))]
+    );
+}
+
+#[test]
+fn withholds_immature_tranches_and_caps_their_number() {
+    let mut suite = setup_unbonding();
+
+    suite.unstake(ALICE, 100).unwrap();
+    wait(&mut suite, UNBONDING_SECONDS - 1);
+    suite.claim(ALICE).unwrap();
+    assert_eq!(suite.balance(ALICE, STAKE_DENOM), 0);
+    assert_eq!(unbondings(&suite).len(), 1);
+
+    for _ in 1..20 {
+        wait(&mut suite, 1);
+        suite.unstake(ALICE, 10).unwrap();
+    }
+    wait(&mut suite, 1);
+    assert_err(
+        suite.unstake(ALICE, 10),
+        "cannot exceed 20 unbonding tranches",
+    );
+
+    // Claiming the matured tranche makes room for another
+    suite.claim(ALICE).unwrap();
+    assert_eq!(suite.balance(ALICE, STAKE_DENOM), 100);
+    suite.unstake(ALICE, 10).unwrap();
+    assert_eq!(unbondings(&suite).len(), 20);
+
+    assert_err(
+        suite.unstake(ALICE, 1_000),
+        "amount exceeds unlocked delegation of 700",
+    );
+}
+
+#[test]
+fn rejects_unstaking_nothing() {
+    let mut suite = setup_unbonding();
+
+    assert_err(suite.unstake(ALICE, 0), "amount must be greater than zero");
+    assert!(unbondings(&suite).is_empty());
+
+    // Without an amount, everything unlocked is unstaked, which can't be
+    // repeated once nothing is left
+    suite
+        .execute(
+            ALICE,
+            &ExecuteMsg::Unstake(UnstakeMsg {
+                amount: None,
+                address: None,
+                instant: None,
+            }),
+            &[],
+        )
+        .unwrap();
+    assert_eq!(unbondings(&suite).len(), 1);
+    assert_err(
+        suite.execute(
+            ALICE,
+            &ExecuteMsg::Unstake(UnstakeMsg {
+                amount: None,
+                address: None,
+                instant: None,
+            }),
+            &[],
+        ),
+        "amount must be greater than zero",
+    );
+    assert_eq!(unbondings(&suite).len(), 1);
+}
+
+#[test]
+fn cancelled_unbonding_earns_from_the_next_deposit() {
+    let mut suite = setup_unbonding();
+    let t0 = suite.app.block_info().time;
+    suite.stake(BOB, 1_000).unwrap();
+
+    suite.unstake(ALICE, 200).unwrap();
+    wait(&mut suite, 10);
+    suite.unstake(ALICE, 300).unwrap();
+
+    suite.deposit(MANAGER, 1_500, STAKE_DENOM).unwrap();
+    assert_eq!(suite.pending(ALICE, STAKE_DENOM), 500);
+
+    // The newest tranche is cancelled first
+    cancel_unbonding(&mut suite, ALICE, Some(400)).unwrap();
+    assert_eq!(
+        unbondings(&suite),
+        vec![tranche(100, t0.plus_seconds(UNBONDING_SECONDS))]
+    );
+    assert_eq!(suite.account(ALICE).unwrap().delegation, Uint128::new(900));
+
+    suite.deposit(MANAGER, 1_900, STAKE_DENOM).unwrap();
+    assert_eq!(suite.pending(ALICE, STAKE_DENOM), 500 + 900);
+    assert_eq!(suite.pending(BOB, STAKE_DENOM), 1_000 + 1_000);
+
+    // Without an amount, all remaining unbonding is cancelled
+    cancel_unbonding(&mut suite, ALICE, None).unwrap();
+    assert!(unbondings(&suite).is_empty());
+    assert_eq!(
+        suite.account(ALICE).unwrap().delegation,
+        Uint128::new(1_000)
+    );
+
+    let house = suite.query::<HouseResponse>(&QueryMsg::House {});
+    assert_eq!(house.delegation.amount, Uint128::new(2_000));
+
+    // Nothing is left to unbond once the first tranche would have matured
+    wait(&mut suite, UNBONDING_SECONDS);
+    suite.claim(ALICE).unwrap();
+    assert_eq!(suite.balance(ALICE, STAKE_DENOM), 500 + 900);
+}
+
+#[test]
+fn rejects_cancelling_more_than_is_unbonding() {
+    let mut suite = setup_unbonding();
+
+    assert_err(
+        cancel_unbonding(&mut suite, ALICE, None),
+        "amount must be greater than zero and at most 0",
+    );
+    assert_err(cancel_unbonding(&mut suite, BOB, None), "Account not found");
+
+    suite.unstake(ALICE, 100).unwrap();
+    assert_err(
+        cancel_unbonding(&mut suite, ALICE, Some(101)),
+        "at most 100",
+    );
+    assert_err(cancel_unbonding(&mut suite, ALICE, Some(0)), "at most 100");
+    assert_eq!(unbondings(&suite).len(), 1);
+    assert_eq!(suite.account(ALICE).unwrap().delegation, Uint128::new(900));
+}
+
+#[test]
+fn instant_unstake_redistributes_the_penalty() {
+    let mut suite = setup_unbonding();
+    suite.stake(BOB, 1_000).unwrap();
+    suite
+        .admin(AdminMsg::AddTaxRecipient(TaxRecipientInitArgs {
+            address: Addr::unchecked("treasury"),
+            name: None,
+            logo: None,
+            pct: Uint128::new(100_000),
+            autosend: true,
+            immutable: false,
+        }))
+        .unwrap();
+
+    // The 50 penalty is taxed 5, leaving 45 split 1:2 by remaining stake
+    enable_instant_unstake(&mut suite, true);
+    instant_unstake(&mut suite, ALICE, None, 500).unwrap();
+
+    assert_eq!(suite.balance(ALICE, STAKE_DENOM), 450);
+    assert_eq!(suite.balance("treasury", STAKE_DENOM), 5);
+    assert_eq!(suite.pending(ALICE, STAKE_DENOM), 15);
+    assert_eq!(suite.pending(BOB, STAKE_DENOM), 30);
+    assert!(unbondings(&suite).is_empty());
+
+    // Untaxed, the whole penalty goes to the remaining stakers
+    enable_instant_unstake(&mut suite, false);
+    instant_unstake(&mut suite, BOB, None, 500).unwrap();
+
+    assert_eq!(suite.balance(BOB, STAKE_DENOM), 450);
+    assert_eq!(suite.balance("treasury", STAKE_DENOM), 5);
+    assert_eq!(suite.pending(ALICE, STAKE_DENOM), 15 + 25);
+    assert_eq!(suite.pending(BOB, STAKE_DENOM), 30 + 25);
+
+    let house = suite.query::<HouseResponse>(&QueryMsg::House {});
+    assert_eq!(house.delegation.amount, Uint128::new(1_000));
+}
+
+#[test]
+fn waives_the_penalty_of_the_last_staker() {
+    let mut suite = setup_unbonding();
+    enable_instant_unstake(&mut suite, false);
+
+    instant_unstake(&mut suite, ALICE, None, 1_000).unwrap();
+
+    assert_eq!(suite.balance(ALICE, STAKE_DENOM), 1_000);
+    assert!(suite.account(ALICE).is_none());
+}
+
+#[test]
+fn rejects_instant_unstake_when_disabled_or_on_behalf_of_others() {
+    let mut suite = setup_unbonding();
+
+    assert_err(
+        instant_unstake(&mut suite, ALICE, None, 100),
+        "instant unstake is disabled",
+    );
+    assert_err(
+        suite.admin(AdminMsg::SetInstantUnstakeConfig {
+            config: Some(InstantUnstakeConfig {
+                penalty_pct: Uint128::new(1_000_001),
+                taxed: false,
+            }),
+        }),
+        "penalty rate cannot exceed",
+    );
+
+    enable_instant_unstake(&mut suite, false);
+    assert_err(
+        instant_unstake(&mut suite, MANAGER, Some(ALICE), 100),
+        "only the account owner can unstake instantly",
+    );
+    assert_err(
+        instant_unstake(&mut suite, BOB, Some(ALICE), 100),
+        "only the contract manager can perform this action",
+    );
+
+    // The manager can still start a regular unbonding on Alice's behalf
+    suite
+        .execute(
+            MANAGER,
+            &ExecuteMsg::Unstake(UnstakeMsg {
+                amount: Some(Uint128::new(100)),
+                address: Some(Addr::unchecked(ALICE)),
+                instant: None,
+            }),
+            &[],
+        )
+        .unwrap();
+    assert_eq!(unbondings(&suite).len(), 1);
+    assert_eq!(suite.balance(ALICE, STAKE_DENOM), 0);
+}
+
+#[test]
+fn instant_unstake_penalty_is_neither_burned_nor_a_deposit() {
+    let mut msg: InstantiateMsg = instantiate_msg();
+    msg.staking.revenue_tokens = vec![Token::Denom("factory/contract0/urev".to_owned())];
+    msg.burn_pct = Some(Uint128::new(500_000));
+    let mut suite = setup(msg).unwrap();
+    suite.stake(ALICE, 2_000).unwrap();
+    suite.stake(BOB, 1_000).unwrap();
+    enable_instant_unstake(&mut suite, false);
+
+    // The unburnable staking token isn't burned, and the whole 100 penalty
+    // goes to the remaining stakers
+    instant_unstake(&mut suite, ALICE, None, 1_000).unwrap();
+
+    assert_eq!(suite.balance(ALICE, STAKE_DENOM), 900);
+    assert_eq!(suite.pending(ALICE, STAKE_DENOM), 50);
+    assert_eq!(suite.pending(BOB, STAKE_DENOM), 50);
+
+    let deposits = suite.query::<DepositsResponse>(&QueryMsg::Deposits {
+        token: None,
+        start_after: None,
+        limit: None,
+    });
+    assert!(deposits.totals.is_empty());
+}